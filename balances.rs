@@ -1,8 +1,7 @@
 #[cfg(feature = "enumerable")]
 pub mod balance_manager {
-    use crate::{data::Id, PSP34Error};
+    use crate::{codes, data::Id, PSP34Error};
     use ink::{primitives::AccountId, storage::Mapping};
-    use ink::prelude::string::String;
 
     #[ink::storage_item]
     #[derive(Default, Debug)]
@@ -29,18 +28,14 @@ pub mod balance_manager {
             let mut to_balance = self.balance_of(owner);
             to_balance = to_balance
                 .checked_add(1)
-                .ok_or(PSP34Error::Custom(String::from(
-                    "Max PSP34 balance exceeded. Max balance limited to 2^32-1.",
-                )))?;
+                .ok_or(PSP34Error::custom(codes::MAX_BALANCE_EXCEEDED))?;
             self.owned_tokens_count.insert(owner, &to_balance);
 
             if increase_supply {
-                self.total_supply =
-                    self.total_supply
-                        .checked_add(1)
-                        .ok_or(PSP34Error::Custom(String::from(
-                            "Max PSP34 supply exceeded. Max supply limited to 2^128-1.",
-                        )))?;
+                self.total_supply = self
+                    .total_supply
+                    .checked_add(1)
+                    .ok_or(PSP34Error::custom(codes::MAX_TOTAL_SUPPLY_EXCEEDED))?;
             }
 
             Ok(())
@@ -94,10 +89,17 @@ pub mod balance_manager {
                 .ok_or(PSP34Error::TokenNotExists)
         }
 
+        pub fn owners_token_index(&self, owner: AccountId, id: &Id) -> Option<u128> {
+            self.enumerable
+                .get(Some(owner))
+                .and_then(|values| values.iter().position(|v| v == id))
+                .map(|pos| pos as u128)
+        }
+
         fn _get_value(&self, key: &Option<AccountId>, index: u128) -> Option<Id> {
             self.enumerable
                 .get(key)
-                .and_then(|values| values.get(usize::try_from(index).unwrap()).cloned())
+                .and_then(|values| values.get(usize::try_from(index).ok()?).cloned())
         }
 
         fn _insert(&mut self, key: &Option<AccountId>, value: &Id) {
@@ -118,9 +120,7 @@ pub mod balance_manager {
         fn _count(&self, key: &Option<AccountId>) -> u128 {
             self.enumerable
                 .get(key)
-                .map_or(0, |values| values.len())
-                .try_into()
-                .unwrap()
+                .map_or(0, |values| values.len() as u128)
         }
 
         pub fn balance_of(&self, owner: &AccountId) -> u32 {
@@ -141,7 +141,7 @@ pub mod balance_manager {
             Ok(())
         }
 
-        pub fn decrease_balance(&mut self, owner: &AccountId, id: &Id, decrease_supply: bool) {              
+        pub fn decrease_balance(&mut self, owner: &AccountId, id: &Id, decrease_supply: bool) {
             self._remove(&Some(*owner), id);
             if self.balance_of(owner) == 0 {
                 self.enumerable.remove(Some(owner));
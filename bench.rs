@@ -0,0 +1,105 @@
+//! Storage read/write counts of the core operations, measured on the off-chain engine.
+//!
+//! Run with `cargo bench --features bench`. The output is a CSV table
+//! (`operation,reads,writes`) meant to be diffed between commits.
+#![allow(clippy::expect_used)]
+
+use crate::psp34_nft::Psp34Nft;
+use crate::{Id, PSP34Burnable, PSP34};
+use ink::env::{test, DefaultEnvironment};
+use ink::prelude::{format, string::String, vec::Vec};
+use ink::primitives::AccountId;
+
+/// Upper bound on the storage writes of a single `transfer`.
+pub const TRANSFER_MAX_WRITES: usize = 13;
+
+pub struct BenchRow {
+    pub operation: String,
+    pub reads: usize,
+    pub writes: usize,
+}
+
+fn measure<F: FnOnce()>(operation: String, f: F) -> BenchRow {
+    let callee = test::callee::<DefaultEnvironment>();
+    let (reads_before, writes_before) =
+        test::get_contract_storage_rw::<DefaultEnvironment>(&callee);
+    f();
+    let (reads_after, writes_after) = test::get_contract_storage_rw::<DefaultEnvironment>(&callee);
+    BenchRow {
+        operation,
+        reads: reads_after - reads_before,
+        writes: writes_after - writes_before,
+    }
+}
+
+/// Every scenario gets its own contract account so storage does not leak between them.
+fn new_contract(scenario: u8, owner: AccountId) -> Psp34Nft {
+    test::set_callee::<DefaultEnvironment>(AccountId::from([scenario; 32]));
+    test::set_caller::<DefaultEnvironment>(owner);
+    Psp34Nft::new(owner, String::from("Bench"), String::from("BENCH"))
+}
+
+fn attributes(count: usize) -> Vec<(String, String)> {
+    (0..count)
+        .map(|i| (format!("trait_{}", i), format!("value_{}", i)))
+        .collect()
+}
+
+/// Runs every scenario and returns one row per operation.
+pub fn run() -> Vec<BenchRow> {
+    let mut rows = Vec::new();
+    test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let mut scenario = 0u8;
+        let mut next_contract = || {
+            scenario += 1;
+            new_contract(scenario, accounts.alice)
+        };
+
+        let mut contract = next_contract();
+        rows.push(measure(String::from("mint"), || {
+            contract.mint().expect("mint failed");
+        }));
+
+        for count in [1, 5, 20] {
+            let mut contract = next_contract();
+            rows.push(measure(format!("mint_with_attributes/{}", count), || {
+                contract
+                    .mint_with_attributes(attributes(count))
+                    .expect("mint_with_attributes failed");
+            }));
+        }
+
+        for held in [1u64, 100, 1000] {
+            let mut contract = next_contract();
+            for _ in 0..held {
+                contract.mint().expect("mint failed");
+            }
+            rows.push(measure(format!("transfer/{}", held), || {
+                contract
+                    .transfer(accounts.bob, Id::U64(held), Vec::new())
+                    .expect("transfer failed");
+            }));
+        }
+
+        let mut contract = next_contract();
+        contract.mint().expect("mint failed");
+        rows.push(measure(String::from("burn"), || {
+            contract
+                .burn(accounts.alice, Id::U64(1))
+                .expect("burn failed");
+        }));
+
+        Ok(())
+    })
+    .expect("bench scenarios failed");
+    rows
+}
+
+/// Formats the rows as a CSV table.
+pub fn to_csv(rows: &[BenchRow]) -> String {
+    let mut table = String::from("operation,reads,writes\n");
+    for row in rows {
+        table.push_str(&format!("{},{},{}\n", row.operation, row.reads, row.writes));
+    }
+    table
+}
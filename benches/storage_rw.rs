@@ -0,0 +1,19 @@
+use psp34_nft::bench;
+
+fn main() {
+    let rows = bench::run();
+    print!("{}", bench::to_csv(&rows));
+
+    for row in rows
+        .iter()
+        .filter(|row| row.operation.starts_with("transfer/"))
+    {
+        assert!(
+            row.writes <= bench::TRANSFER_MAX_WRITES,
+            "{} wrote {} storage cells, expected at most {}",
+            row.operation,
+            row.writes,
+            bench::TRANSFER_MAX_WRITES
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::balances::balance_manager::Balances;
-use crate::PSP34Error;
+use crate::{codes, PSP34Error};
 use ink::{
-    prelude::{string::String, vec, vec::Vec},
+    env::{DefaultEnvironment, Environment},
+    prelude::{vec, vec::Vec},
     primitives::AccountId,
     storage::Mapping,
 };
@@ -43,8 +44,18 @@ pub enum PSP34Event {
         key: Vec<u8>,
         data: Vec<u8>,
     },
+    DelegateChanged {
+        id: Id,
+        from: Option<AccountId>,
+        to: Option<AccountId>,
+    },
 }
 
+type OperatorApprovals = Mapping<(AccountId, AccountId, Option<Id>), ()>;
+type TokenApprovals = Mapping<Id, Vec<(AccountId, AccountId)>>;
+type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+type Checkpoints = Mapping<AccountId, Vec<(BlockNumber, u32)>>;
+
 /// A class implementing the internal logic of a PSP34 token.
 //
 /// Holds the state of all account balances and approvals.
@@ -61,7 +72,19 @@ pub enum PSP34Event {
 #[derive(Debug, Default)]
 pub struct PSP34Data {
     token_owner: Mapping<Id, AccountId>,
-    operator_approvals: Mapping<(AccountId, AccountId, Option<Id>), ()>,
+    operator_approvals: OperatorApprovals,
+    token_approvals: TokenApprovals,
+    owner_operators: Mapping<AccountId, Vec<AccountId>>,
+    ever_held: Mapping<AccountId, bool>,
+    /// `(block_number, balance)` after each block in which the balance changed, oldest first.
+    /// Grows by one entry per block with a balance change and is never pruned.
+    checkpoints: Checkpoints,
+    holders: Mapping<u32, AccountId>,
+    holder_index: Mapping<AccountId, u32>,
+    holder_count: u32,
+    delegates: Mapping<Id, AccountId>,
+    delegated_count: Mapping<AccountId, u32>,
+    total_transfers: u64,
     balance: Balances,
 }
 
@@ -84,6 +107,11 @@ impl PSP34Data {
         self.token_owner.get(id)
     }
 
+    /// Returns `true` if `account` has ever received a token, even if it no longer holds any.
+    pub fn has_ever_held(&self, account: AccountId) -> bool {
+        self.ever_held.get(account).unwrap_or(false)
+    }
+
     pub fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<&Id>) -> bool {
         self.operator_approvals
             .get((owner, operator, &None))
@@ -116,19 +144,31 @@ impl PSP34Data {
             }
 
             if !approved && self.allowance(owner, operator, None) {
-                return Err(PSP34Error::Custom(String::from(
-                    "Cannot revoke approval for a single token, when the operator has approval for all tokens."
-                )));
+                return Err(PSP34Error::custom(codes::CANNOT_REVOKE_SINGLE_APPROVAL));
             }
             caller = owner;
         }
 
         if approved {
-            self.operator_approvals
-                .insert((caller, operator, id.as_ref()), &());
+            if self
+                .operator_approvals
+                .insert((caller, operator, id.as_ref()), &())
+                .is_none()
+            {
+                if let Some(id) = &id {
+                    self._index_token_approval(id, caller, operator);
+                } else {
+                    self._index_operator(caller, operator);
+                }
+            }
         } else {
             self.operator_approvals
                 .remove((caller, operator, id.as_ref()));
+            if let Some(id) = &id {
+                self._unindex_token_approval(id, caller, operator);
+            } else {
+                self._unindex_operator(caller, operator);
+            }
         }
 
         Ok(vec![PSP34Event::Approval {
@@ -158,18 +198,29 @@ impl PSP34Data {
         }
 
         self.balance.decrease_balance(&owner, &id, false);
+        self._checkpoint(owner);
+        self._update_holder(owner);
 
-        self.operator_approvals.remove((owner, caller, Some(&id)));
+        let mut events = self._clear_token_approvals(&id);
+        events.extend(self._set_delegate(&id, None));
         self.token_owner.remove(&id);
 
         self.token_owner.insert(&id, &to);
         self.balance.increase_balance(&to, &id, false)?;
+        self._checkpoint(to);
+        self._update_holder(to);
+        self.ever_held.insert(to, &true);
+        self.total_transfers = self
+            .total_transfers
+            .checked_add(1)
+            .ok_or(PSP34Error::custom(codes::MAX_TRANSFER_COUNT_EXCEEDED))?;
 
-        Ok(vec![PSP34Event::Transfer {
+        events.push(PSP34Event::Transfer {
             from: Some(caller),
             to: Some(to),
             id,
-        }])
+        });
+        Ok(events)
     }
 
     /// Mints a token `id` to `account`.
@@ -178,7 +229,10 @@ impl PSP34Data {
             return Err(PSP34Error::TokenExists);
         }
         self.balance.increase_balance(&account, &id, true)?;
+        self._checkpoint(account);
+        self._update_holder(account);
         self.token_owner.insert(&id, &account);
+        self.ever_held.insert(account, &true);
 
         Ok(vec![PSP34Event::Transfer {
             from: None,
@@ -201,13 +255,210 @@ impl PSP34Data {
             return Err(PSP34Error::NotApproved);
         }
         self.balance.decrease_balance(&account, &id, true);
+        self._checkpoint(account);
+        self._update_holder(account);
+        let mut events = self._clear_token_approvals(&id);
+        events.extend(self._set_delegate(&id, None));
         self.token_owner.remove(&id);
 
-        Ok(vec![PSP34Event::Transfer {
+        events.push(PSP34Event::Transfer {
             from: Some(account),
             to: None,
             id,
-        }])
+        });
+        Ok(events)
+    }
+
+    /// Returns the balance of `owner` at the end of block `block`.
+    pub fn balance_of_at(&self, owner: AccountId, block: BlockNumber) -> u32 {
+        let checkpoints = self.checkpoints.get(owner).unwrap_or_default();
+        let count = checkpoints.partition_point(|(number, _)| *number <= block);
+        count
+            .checked_sub(1)
+            .and_then(|last| checkpoints.get(last))
+            .map_or(0, |(_, balance)| *balance)
+    }
+
+    /// Returns the number of accounts holding at least one token.
+    pub fn holder_count(&self) -> u32 {
+        self.holder_count
+    }
+
+    /// Returns the number of owner-to-owner transfers. Mints and burns are not counted.
+    pub fn total_transfers(&self) -> u64 {
+        self.total_transfers
+    }
+
+    /// Returns the holder at `index`, for `index` below `holder_count`.
+    /// The order changes when holders leave.
+    pub fn holder_by_index(&self, index: u32) -> Option<AccountId> {
+        self.holders.get(index)
+    }
+
+    /// Adds `account` to the holders when it gets its first token and removes it,
+    /// moving the last holder into its slot, when it no longer holds any.
+    fn _update_holder(&mut self, account: AccountId) {
+        let holds = self.balance.balance_of(&account) > 0;
+        match (self.holder_index.get(account), holds) {
+            (None, true) => {
+                self.holders.insert(self.holder_count, &account);
+                self.holder_index.insert(account, &self.holder_count);
+                self.holder_count += 1;
+            }
+            (Some(index), false) => {
+                self.holder_count -= 1;
+                let last = self.holder_count;
+                if index != last {
+                    if let Some(moved) = self.holders.get(last) {
+                        self.holders.insert(index, &moved);
+                        self.holder_index.insert(moved, &index);
+                    }
+                }
+                self.holders.remove(last);
+                self.holder_index.remove(account);
+            }
+            _ => {}
+        }
+    }
+
+    /// Records the current balance of `account` for the current block.
+    fn _checkpoint(&mut self, account: AccountId) {
+        let block = ink::env::block_number::<DefaultEnvironment>();
+        let balance = self.balance.balance_of(&account);
+        let mut checkpoints = self.checkpoints.get(account).unwrap_or_default();
+        match checkpoints.last_mut() {
+            Some((number, value)) if *number == block => *value = balance,
+            _ => checkpoints.push((block, balance)),
+        }
+        self.checkpoints.insert(account, &checkpoints);
+    }
+
+    /// Delegates the rights attached to token `id` to `to`, or removes the delegation with `None`.
+    /// Only the owner of the token can delegate it.
+    pub fn delegate(
+        &mut self,
+        caller: AccountId,
+        id: Id,
+        to: Option<AccountId>,
+    ) -> Result<Vec<PSP34Event>, PSP34Error> {
+        let owner = self.owner_of(&id).ok_or(PSP34Error::TokenNotExists)?;
+        if owner != caller {
+            return Err(PSP34Error::NotApproved);
+        }
+        Ok(self._set_delegate(&id, to).into_iter().collect())
+    }
+
+    /// Returns the account token `id` is delegated to, if any.
+    pub fn delegate_of(&self, id: &Id) -> Option<AccountId> {
+        self.delegates.get(id)
+    }
+
+    /// Returns the number of tokens delegated to `account`.
+    pub fn delegated_count(&self, account: AccountId) -> u32 {
+        self.delegated_count.get(account).unwrap_or(0)
+    }
+
+    /// Replaces the delegate of `id`, returning a `DelegateChanged` event if it changed.
+    fn _set_delegate(&mut self, id: &Id, to: Option<AccountId>) -> Option<PSP34Event> {
+        let from = self.delegates.get(id);
+        if from == to {
+            return None;
+        }
+        if let Some(from) = from {
+            self.delegates.remove(id);
+            let count = self.delegated_count(from).saturating_sub(1);
+            if count == 0 {
+                self.delegated_count.remove(from);
+            } else {
+                self.delegated_count.insert(from, &count);
+            }
+        }
+        if let Some(to) = to {
+            self.delegates.insert(id, &to);
+            self.delegated_count
+                .insert(to, &self.delegated_count(to).saturating_add(1));
+        }
+        Some(PSP34Event::DelegateChanged {
+            id: id.clone(),
+            from,
+            to,
+        })
+    }
+
+    /// Returns the owner who granted a still valid per-token approval for `id`, if any.
+    /// Approvals granted by previous owners are no longer valid after a transfer.
+    pub fn approval_granter(&self, id: &Id) -> Option<AccountId> {
+        let owner = self.owner_of(id)?;
+        self.token_approvals
+            .get(id)?
+            .iter()
+            .any(|(granter, _)| *granter == owner)
+            .then_some(owner)
+    }
+
+    /// Returns the operators approved by `owner` for all of its tokens.
+    pub fn operators_of(&self, owner: AccountId) -> Vec<AccountId> {
+        self.owner_operators.get(owner).unwrap_or_default()
+    }
+
+    pub fn operator_count(&self, owner: AccountId) -> u32 {
+        self.operators_of(owner).len() as u32
+    }
+
+    /// Removes every per-token approval granted for `id`, returning an
+    /// `Approval` revocation event for each of them.
+    fn _clear_token_approvals(&mut self, id: &Id) -> Vec<PSP34Event> {
+        let mut events = Vec::new();
+        if let Some(approvals) = self.token_approvals.take(id) {
+            for (owner, operator) in approvals {
+                self.operator_approvals.remove((owner, operator, Some(id)));
+                events.push(PSP34Event::Approval {
+                    owner,
+                    operator,
+                    id: Some(id.clone()),
+                    approved: false,
+                });
+            }
+        }
+        events
+    }
+
+    fn _index_operator(&mut self, owner: AccountId, operator: AccountId) {
+        let mut operators = self.owner_operators.get(owner).unwrap_or_default();
+        operators.push(operator);
+        self.owner_operators.insert(owner, &operators);
+    }
+
+    fn _unindex_operator(&mut self, owner: AccountId, operator: AccountId) {
+        if let Some(mut operators) = self.owner_operators.get(owner) {
+            if let Some(pos) = operators.iter().position(|v| *v == operator) {
+                operators.swap_remove(pos);
+                if operators.is_empty() {
+                    self.owner_operators.remove(owner);
+                } else {
+                    self.owner_operators.insert(owner, &operators);
+                }
+            }
+        }
+    }
+
+    fn _index_token_approval(&mut self, id: &Id, owner: AccountId, operator: AccountId) {
+        let mut approvals = self.token_approvals.get(id).unwrap_or_default();
+        approvals.push((owner, operator));
+        self.token_approvals.insert(id, &approvals);
+    }
+
+    fn _unindex_token_approval(&mut self, id: &Id, owner: AccountId, operator: AccountId) {
+        if let Some(mut approvals) = self.token_approvals.get(id) {
+            if let Some(pos) = approvals.iter().position(|v| *v == (owner, operator)) {
+                approvals.swap_remove(pos);
+                if approvals.is_empty() {
+                    self.token_approvals.remove(id);
+                } else {
+                    self.token_approvals.insert(id, &approvals);
+                }
+            }
+        }
     }
 
     #[cfg(not(feature = "enumerable"))]
@@ -215,6 +466,11 @@ impl PSP34Data {
         self.balance.owners_token_by_index(owner, index)
     }
 
+    #[cfg(not(feature = "enumerable"))]
+    pub fn owners_token_index(&self, owner: AccountId, id: &Id) -> Option<u128> {
+        self.balance.owners_token_index(owner, id)
+    }
+
     #[cfg(not(feature = "enumerable"))]
     pub fn token_by_index(&self, index: u128) -> Result<Id, PSP34Error> {
         self.balance.token_by_index(index)
@@ -227,15 +483,22 @@ impl Default for Id {
     }
 }
 
-impl From<Id> for u128 {
-    fn from(id: Id) -> Self {
+impl TryFrom<Id> for u128 {
+    type Error = PSP34Error;
+
+    /// Fails for `Id::Bytes` that are not exactly 16 bytes long.
+    fn try_from(id: Id) -> Result<Self, Self::Error> {
         match id {
-            Id::U8(val) => val as u128,
-            Id::U16(val) => val as u128,
-            Id::U32(val) => val as u128,
-            Id::U64(val) => val as u128,
-            Id::U128(val) => val,
-            Id::Bytes(val) => u128::from_be_bytes(val.as_slice().try_into().unwrap()),
+            Id::U8(val) => Ok(val as u128),
+            Id::U16(val) => Ok(val as u128),
+            Id::U32(val) => Ok(val as u128),
+            Id::U64(val) => Ok(val as u128),
+            Id::U128(val) => Ok(val),
+            Id::Bytes(val) => val
+                .as_slice()
+                .try_into()
+                .map(u128::from_be_bytes)
+                .map_err(|_| PSP34Error::custom(codes::ID_DOES_NOT_FIT_IN_U128)),
         }
     }
 }
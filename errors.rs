@@ -1,14 +1,147 @@
 use ink::prelude::string::String;
 
+/// Declares the `codes` constants and the message of each code
+macro_rules! error_codes {
+    ($($name:ident = $code:literal => $message:literal,)*) => {
+        /// Codes of the custom errors raised by this crate. They are returned as
+        /// `Custom(message)`, or as `Code(code)` with the `small-errors` feature so that
+        /// the messages are left out of the Wasm blob. The doc of each code is its message.
+        pub mod codes {
+            $(
+                #[doc = $message]
+                pub const $name: u16 = $code;
+            )*
+        }
+
+        #[cfg(not(feature = "small-errors"))]
+        fn message(code: u16) -> &'static str {
+            match code {
+                $($code => $message,)*
+                _ => "Unknown error",
+            }
+        }
+    };
+}
+
+// Codes are part of the contract interface with `small-errors`: append new ones, never renumber.
+error_codes! {
+    CANNOT_SET_ATTRIBUTES = 1 => "Cannot set attributes",
+    ATTRIBUTES_ARE_REQUIRED = 2 => "Attributes are required",
+    ONLY_THE_LAUNCHPAD_CAN_MINT = 3 => "Only the launchpad can mint",
+    MAX_SUPPLY_REACHED = 4 => "Max supply reached",
+    CANNOT_INCREASE_LAST_TOKEN_ID = 5 => "Cannot increase last token id",
+    ID_IS_NOT_RESERVED = 6 => "Id is not reserved",
+    IMPORT_IS_FINALIZED = 7 => "Import is finalized",
+    BATCH_SIZE_EXCEEDED = 8 => "Batch size exceeded",
+    VOUCHER_SIGNER_IS_NOT_SET = 9 => "Voucher signer is not set",
+    VOUCHER_ALREADY_REDEEMED = 10 => "Voucher already redeemed",
+    INVALID_VOUCHER_SIGNATURE = 11 => "Invalid voucher signature",
+    INVALID_SIGNATURE = 12 => "Invalid signature",
+    ATTRIBUTE_ORACLE_IS_NOT_SET = 13 => "Attribute oracle is not set",
+    INVALID_NONCE = 14 => "Invalid nonce",
+    CANNOT_INCREASE_NONCE = 15 => "Cannot increase nonce",
+    META_TRANSACTION_EXPIRED = 16 => "Meta transaction expired",
+    CALLER_IS_NOT_THE_TRUSTED_FORWARDER = 17 => "Caller is not the trusted forwarder",
+    CALLER_IS_NOT_THE_LAUNCHPAD = 18 => "Caller is not the launchpad",
+    CLAIM_SOURCE_IS_NOT_SET = 19 => "Claim source is not set",
+    SOURCE_TOKEN_ALREADY_CLAIMED = 20 => "Source token already claimed",
+    CANNOT_REACH_CLAIM_SOURCE = 21 => "Cannot reach claim source",
+    CALLER_DOES_NOT_OWN_THE_SOURCE_TOKEN = 22 => "Caller does not own the source token",
+    UPGRADES_ARE_DISABLED = 23 => "Upgrades are disabled",
+    CANNOT_SET_CODE_HASH = 24 => "Cannot set code hash",
+    ADMIN_TRANSFER_IS_DISABLED = 25 => "Admin transfer is disabled",
+    TOKEN_IS_SOULBOUND = 26 => "Token is soulbound",
+    OPERATOR_IS_NOT_AN_ALLOWED_MARKETPLACE = 27 => "Operator is not an allowed marketplace",
+    SNAPSHOT_ALREADY_EXISTS = 28 => "Snapshot already exists",
+    SNAPSHOT_DOES_NOT_EXIST = 29 => "Snapshot does not exist",
+    NOT_TOKEN_OWNER = 30 => "not token owner",
+    CALLER_IS_NOT_TOKEN_OWNER_OR_APPROVED = 31 => "caller is not token owner or approved",
+    NO_TOKEN_OWNER_FOUND = 32 => "No token owner found",
+    TRANSFER_REJECTED_BY_REGISTRY = 33 => "Transfer rejected by registry",
+    ACCOUNT_IS_FROZEN = 34 => "Account is frozen",
+    LOCKED_TOKEN_COUNT_ERROR = 35 => "Locked token count error",
+    SUPPLY_IS_FROZEN = 36 => "Supply is frozen",
+    TOO_MANY_RESERVATIONS = 37 => "Too many reservations",
+    RESERVATION_OVERLAPS = 38 => "Reservation overlaps",
+    RESERVATION_HAS_NOT_EXPIRED = 39 => "Reservation has not expired",
+    ID_IS_RESERVED = 40 => "Id is reserved",
+    SUPPLY_IS_ALREADY_FROZEN = 41 => "Supply is already frozen",
+    PUBLIC_MINT_IS_NOT_ENABLED = 42 => "Public mint is not enabled",
+    EXCEEDS_WHITELIST_ALLOWANCE = 43 => "Exceeds whitelist allowance",
+    EXCEEDS_WALLET_LIMIT = 44 => "Exceeds wallet limit",
+    WHITELIST_IS_FULL = 45 => "Whitelist is full",
+    REFUNDS_ARE_NOT_ENABLED = 46 => "Refunds are not enabled",
+    TOKEN_IS_NOT_REFUNDABLE = 47 => "Token is not refundable",
+    CALLER_IS_NOT_THE_MINTER = 48 => "Caller is not the minter",
+    REFUND_WINDOW_IS_CLOSED = 49 => "Refund window is closed",
+    REVEAL_ESCROW_CANNOT_BE_CHANGED = 50 => "Reveal escrow cannot be changed",
+    ALREADY_REVEALED = 51 => "Already revealed",
+    REVEAL_DEADLINE_HAS_NOT_PASSED = 52 => "Reveal deadline has not passed",
+    ALREADY_LOCKED = 53 => "already locked",
+    CANNOT_INCREASE_LOCKED_TOKEN_COUNT = 54 => "Cannot increase locked token count",
+    TOKEN_IS_NOT_LOCKED = 55 => "Token is not locked",
+    LOCK_IS_FINAL = 56 => "Lock is final",
+    TOKEN_IS_LOCKED = 57 => "Token is locked",
+    ADMIN_OVERRIDE_IS_DISABLED = 58 => "Admin override is disabled",
+    ADMIN_OVERRIDE_IS_ALREADY_DISABLED = 59 => "Admin override is already disabled",
+    ADMIN_TRANSFER_IS_PERMANENTLY_DISABLED = 60 => "Admin transfer is permanently disabled",
+    ADMIN_TRANSFER_IS_ALREADY_DISABLED = 61 => "Admin transfer is already disabled",
+    TOO_MANY_STAGED_TOKENS = 62 => "Too many staged tokens",
+    NO_STAGED_ATTRIBUTES = 63 => "No staged attributes",
+    NAMESPACE_EXISTS = 64 => "Namespace exists",
+    NAMESPACE_NOT_FOUND = 65 => "Namespace not found",
+    CALLER_IS_NOT_THE_NAMESPACE_WRITER = 66 => "Caller is not the namespace writer",
+    ATTRIBUTE_IS_NOT_HOLDER_WRITABLE = 67 => "Attribute is not holder writable",
+    ATTRIBUTE_CHANGE_ALREADY_PROPOSED = 68 => "Attribute change already proposed",
+    NO_ATTRIBUTE_CHANGE_PROPOSED = 69 => "No attribute change proposed",
+    TRANSFER_COOLDOWN = 70 => "transfer cooldown",
+    COMPLIANCE_IS_NOT_ENABLED = 71 => "Compliance is not enabled",
+    ACCOUNT_IS_ALREADY_FROZEN = 72 => "Account is already frozen",
+    ACCOUNT_IS_NOT_FROZEN = 73 => "Account is not frozen",
+    TOKEN_IS_ALREADY_ESCROWED = 74 => "Token is already escrowed",
+    NO_ESCROW_FOUND = 75 => "No escrow found",
+    CALLER_IS_NOT_THE_RECIPIENT = 76 => "Caller is not the recipient",
+    ESCROW_HAS_EXPIRED = 77 => "Escrow has expired",
+    CALLER_IS_NOT_THE_SELLER = 78 => "Caller is not the seller",
+    ESCROW_HAS_NOT_EXPIRED = 79 => "Escrow has not expired",
+    CANNOT_INCREASE_TEMPLATE_VERSION = 80 => "Cannot increase template version",
+    TIER_DOES_NOT_EXIST = 81 => "Tier does not exist",
+    EDITION_ALREADY_EXISTS = 82 => "Edition already exists",
+    EDITION_DOES_NOT_EXIST = 83 => "Edition does not exist",
+    EDITION_SOLD_OUT = 84 => "Edition sold out",
+    TOKEN_URI_IS_LOCKED = 85 => "Token URI is locked",
+    TOO_MANY_URI_RANGES = 86 => "Too many URI ranges",
+    FAIL_TO_INCREASE_ATTRIBUTE_COUNT = 87 => "Fail to increase attribute count",
+    ATTRIBUTE_INPUT_ERROR = 88 => "Attribute input error",
+    CANNOT_REVOKE_SINGLE_APPROVAL = 89 => "Cannot revoke approval for a single token, when the operator has approval for all tokens.",
+    MAX_TRANSFER_COUNT_EXCEEDED = 90 => "Max transfer count exceeded.",
+    ID_DOES_NOT_FIT_IN_U128 = 91 => "Id does not fit in u128",
+    MAX_BALANCE_EXCEEDED = 92 => "Max PSP34 balance exceeded. Max balance limited to 2^32-1.",
+    MAX_TOTAL_SUPPLY_EXCEEDED = 93 => "Max PSP34 supply exceeded. Max supply limited to 2^128-1.",
+}
+
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum Error {
+    #[cfg(not(feature = "small-errors"))]
     Custom(String),
+    #[cfg(feature = "small-errors")]
+    Code(u16),
     NotEnoughBalance,
     WithdrawFeeError,
     OwnableError(OwnableError),
     PSP34Error(PSP34Error),
-    InvalidInput
+    InvalidInput,
+    AccountFrozen,
+    MintAllowanceExceeded,
+    ExceedsTxLimit,
+    TokenWasBurned,
+    TierSoldOut,
+    TooManyAttributes,
+    IndexOutOfRange,
+    InvalidUtf8,
+    BaseUriNotSet,
+    UriRangeOverlap,
 }
 
 impl From<OwnableError> for Error {
@@ -20,7 +153,10 @@ impl From<OwnableError> for Error {
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum OwnableError {
+    #[cfg(not(feature = "small-errors"))]
     Custom(String),
+    #[cfg(feature = "small-errors")]
+    Code(u16),
     CallerIsNotOwner,
     NewOwnerIsNotSet,
 }
@@ -35,7 +171,11 @@ impl From<PSP34Error> for Error {
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum PSP34Error {
     /// Custom error type for cases if writer of traits added own restrictions
+    #[cfg(not(feature = "small-errors"))]
     Custom(String),
+    /// Code of a custom error, see `codes`
+    #[cfg(feature = "small-errors")]
+    Code(u16),
     /// Returned if owner approves self
     SelfApprove,
     /// Returned if the caller doesn't have allowance for transferring.
@@ -46,4 +186,28 @@ pub enum PSP34Error {
     TokenNotExists,
     /// Returned if safe transfer check fails
     SafeTransferCheckFailed(String),
+    /// Returned if the token is locked
+    TokenLocked,
+}
+
+/// Builds the `Custom` variant of `$error` for `code`, or its `Code` variant with `small-errors`
+macro_rules! impl_custom {
+    ($($error:ident),*) => {
+        $(
+            impl $error {
+                pub fn custom(code: u16) -> Self {
+                    #[cfg(not(feature = "small-errors"))]
+                    {
+                        $error::Custom(String::from(message(code)))
+                    }
+                    #[cfg(feature = "small-errors")]
+                    {
+                        $error::Code(code)
+                    }
+                }
+            }
+        )*
+    };
 }
+
+impl_custom!(Error, OwnableError, PSP34Error);
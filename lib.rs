@@ -1,32 +1,140 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![deny(clippy::unwrap_used, clippy::expect_used)]
 
 mod balances;
+#[cfg(all(feature = "bench", not(feature = "contract")))]
+pub mod bench;
 mod data;
 mod errors;
 pub mod metadata;
 pub mod ownable;
 pub mod psp34_standard;
+#[cfg(all(test, not(feature = "contract")))]
+mod tests;
 mod traits;
 
 pub use data::{Id, PSP34Data, PSP34Event};
-pub use errors::{Error, OwnableError, PSP34Error};
+pub use errors::{codes, Error, OwnableError, PSP34Error};
 pub use traits::{Ownable, PSP34Burnable, PSP34Metadata, PSP34Mintable, Psp34Traits, PSP34};
 
 #[cfg(not(feature = "enumerable"))]
 pub use traits::PSP34Enumerable;
 
+/// Discrepancies found by `audit` over one range of token ids
+#[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AuditReport {
+    /// Number of `Id::U64` ids checked
+    pub scanned: u64,
+    /// Existing tokens in the range
+    pub tokens: u64,
+    /// Existing tokens in the range that are locked. Summed over all ranges it should equal `locked_token_count`
+    pub locked: u64,
+    /// The stored `locked_token_count`
+    pub locked_token_count: u64,
+    /// Lock entries left on ids that have no owner
+    pub orphan_locks: u64,
+    /// Tokens whose owner has a zero balance
+    pub owners_without_balance: u64,
+    /// Attribute indices in the range, up to `attribute_count`, with no registered name
+    pub missing_attribute_names: u64,
+    /// Id to start the next call from, or None once past the last token id
+    pub next_start: Option<u64>,
+}
+
+/// The optional behaviours a collection has turned on, returned by `feature_flags`
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FeatureFlags {
+    pub compliance: bool,
+    pub admin_override: bool,
+    pub admin_transfer: bool,
+    pub supply_frozen: bool,
+    pub public_mint: bool,
+    pub notify_on_mint: bool,
+    pub remint_burned: bool,
+    pub require_unlock_to_burn: bool,
+    pub emit_transfer_data: bool,
+    pub escrow_cancel_anytime: bool,
+    pub auto_sync_royalty: bool,
+    pub attribute_history: bool,
+    pub refunds: bool,
+    pub reveal_escrow: bool,
+    pub upgradeable: bool,
+    pub enforced_royalties: bool,
+    pub require_attributes_on_mint: bool,
+}
+
+/// Operation a relayer can execute on behalf of the signer with `execute_meta_tx`
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum MetaPayload {
+    Transfer {
+        to: ink::primitives::AccountId,
+        id: Id,
+    },
+    Approve {
+        operator: ink::primitives::AccountId,
+        id: Option<Id>,
+        approved: bool,
+    },
+    Lock {
+        id: Id,
+    },
+}
+
+/// Call signed by `from` and submitted by a relayer. `nonce` must be `get_meta_nonce(from)` and the call is rejected after `deadline`
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MetaCall {
+    pub from: ink::primitives::AccountId,
+    pub payload: MetaPayload,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
 #[cfg(not(feature = "contract"))]
 #[ink::contract]
 mod psp34_nft {
     use crate::{
-        ownable, psp34_standard, Error, Id, Ownable, OwnableError, PSP34Burnable, PSP34Data,
-        PSP34Error, PSP34Event, PSP34Metadata, PSP34Mintable, Psp34Traits, PSP34,
+        codes, ownable,
+        psp34_standard::{
+            self,
+            manager::{
+                AttributeProposal, BondingCurve, CurveKind, Edition, Escrow, IdReservation,
+                Snapshot, Tier, UriRange,
+            },
+        },
+        AuditReport, Error, FeatureFlags, Id, MetaCall, MetaPayload, Ownable, OwnableError,
+        PSP34Burnable, PSP34Data, PSP34Error, PSP34Event, PSP34Metadata, Psp34Traits, PSP34,
+    };
+    use ink::env::{
+        call::{build_call, ExecutionInput, Selector},
+        hash::{Blake2x256, HashOutput},
+        DefaultEnvironment,
     };
     use ink::prelude::{string::String, vec::Vec};
 
     #[cfg(not(feature = "enumerable"))]
     use crate::PSP34Enumerable;
 
+    /// Version of the contract logic, bumped on every release
+    const VERSION: u32 = 1;
+
+    /// Gas limit of the `is_allowed` call made to the transfer registry
+    const REGISTRY_GAS_LIMIT: u64 = 5_000_000_000;
+
+    /// Token id, owner and attributes of a token recreated by `import_tokens`
+    type ImportEntry = (Id, AccountId, Vec<(String, String)>);
+
+    /// Maximum number of token ids checked by one `audit` call
+    const MAX_AUDIT_RANGE: u64 = 500;
+
+    /// Maximum number of items accepted by the batch messages
+    const MAX_BATCH_SIZE: usize = 50;
+    /// Maximum number of NFTs minted by a single batch mint
+    const MAX_MINT_BATCH_SIZE: u32 = 100;
+
     #[ink(storage)]
     #[derive(Default)]
     pub struct Psp34Nft {
@@ -35,76 +143,2773 @@ mod psp34_nft {
         manager_psp34_standard: psp34_standard::manager::Manager,
     }
 
-    impl Psp34Nft {
-        #[ink(constructor)]
-        pub fn new(contract_owner: AccountId, name: String, symbol: String) -> Self {
-            let mut instance = Self::default();
-            instance.ownable._init_with_owner(contract_owner);
-            instance
-                .manager_psp34_standard
-                .metadata
-                .set_attribute(
-                    Id::U8(0),
-                    String::from("name").into_bytes(),
-                    name.into_bytes(),
-                )
-                .expect("Failed to set attribute");
-            instance
-                .manager_psp34_standard
-                .metadata
-                .set_attribute(
-                    Id::U8(0),
-                    String::from("symbol").into_bytes(),
-                    symbol.into_bytes(),
-                )
-                .expect("Failed to set attribute");
-            instance
+    impl Psp34Nft {
+        #[ink(constructor)]
+        pub fn new(contract_owner: AccountId, name: String, symbol: String) -> Self {
+            Self::new_with_compliance(contract_owner, name, symbol, false)
+        }
+
+        /// Same as `new`, with `compliance_enabled` letting the Contract Owner freeze accounts. Collections created without it can never freeze an account
+        #[ink(constructor)]
+        pub fn new_with_compliance(
+            contract_owner: AccountId,
+            name: String,
+            symbol: String,
+            compliance_enabled: bool,
+        ) -> Self {
+            let mut instance = Self::default();
+            instance.manager_psp34_standard.compliance_enabled = compliance_enabled;
+            instance.ownable._init_with_owner(contract_owner);
+            instance
+                .manager_psp34_standard
+                .admin_override_enabled
+                .set(&true);
+            instance.manager_psp34_standard.creator = Some(contract_owner);
+            instance.manager_psp34_standard.metadata.insert_attribute(
+                &Id::U8(0),
+                &String::from("name").into_bytes(),
+                &name.into_bytes(),
+            );
+            instance.manager_psp34_standard.metadata.insert_attribute(
+                &Id::U8(0),
+                &String::from("symbol").into_bytes(),
+                &symbol.into_bytes(),
+            );
+            instance
+        }
+
+        /// This function let NFT Contract Owner to mint a new NFT without providing NFT Traits/Attributes
+        #[ink(message)]
+        pub fn mint(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self._check_owner_mint(caller)?;
+            self._check_bare_mint()?;
+            self._mint_next(caller)?;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to mint a new NFT and approve `operator` to transfer it in the same transaction, for example to list it on a marketplace
+        #[ink(message)]
+        pub fn mint_and_approve(&mut self, operator: AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self._check_owner_mint(caller)?;
+            self._check_bare_mint()?;
+            let token_id = self._mint_next(caller)?;
+            self.approve(operator, Some(token_id), true)?;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to mint a new NFT with NFT Traits/Attributes
+        #[ink(message)]
+        pub fn mint_with_attributes(
+            &mut self,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self._check_owner_mint(caller)?;
+            if metadata.is_empty() {
+                self._check_bare_mint()?;
+            }
+            let token_id = self._mint_next(caller)?;
+            if self
+                .manager_psp34_standard
+                .set_attributes_unchecked_for_new_token(&token_id, metadata)
+                .is_err()
+            {
+                return Err(Error::custom(codes::CANNOT_SET_ATTRIBUTES));
+            }
+            Ok(())
+        }
+
+        /// Mints the next sequential token id to `to`
+        fn _mint_next(&mut self, to: AccountId) -> Result<Id, Error> {
+            let (token_id, events) = self._mint_next_without_event(to)?;
+            self.emit_events(events);
+            self._emit_sold_out();
+            Ok(token_id)
+        }
+
+        /// Emits `SoldOut` the first time the last token id reaches the max supply
+        fn _emit_sold_out(&mut self) {
+            if self.manager_psp34_standard.mark_sold_out() {
+                self.env().emit_event(SoldOut {
+                    total_supply: self.manager_psp34_standard.max_supply,
+                });
+            }
+        }
+
+        /// Reject owner mints without attributes when attributes are required
+        fn _check_bare_mint(&self) -> Result<(), Error> {
+            if self.manager_psp34_standard.require_attributes_on_mint {
+                return Err(Error::custom(codes::ATTRIBUTES_ARE_REQUIRED));
+            }
+            Ok(())
+        }
+
+        /// Check `caller` is the NFT Contract Owner and owner mints are not disabled by the launchpad
+        fn _check_owner_mint(&self, caller: AccountId) -> Result<(), Error> {
+            self.ownable._check_owner(Some(caller))?;
+            if self.manager_psp34_standard.launchpad.is_some()
+                && self.manager_psp34_standard.launchpad_only_mint
+            {
+                return Err(Error::custom(codes::ONLY_THE_LAUNCHPAD_CAN_MINT));
+            }
+            Ok(())
+        }
+
+        /// Mints the next sequential token id to `to`, leaving the events to the caller
+        fn _mint_next_without_event(
+            &mut self,
+            to: AccountId,
+        ) -> Result<(Id, Vec<PSP34Event>), Error> {
+            self.manager_psp34_standard.check_mintable()?;
+            if let Some(last_token_id) = self._next_sequential_id() {
+                let max_supply = self.manager_psp34_standard.max_supply;
+                if max_supply != 0 && last_token_id > max_supply {
+                    return Err(Error::custom(codes::MAX_SUPPLY_REACHED));
+                }
+                self.manager_psp34_standard.last_token_id = last_token_id;
+                let token_id = Id::U64(last_token_id);
+                let events = self._mint_id_without_event(to, token_id.clone())?;
+                Ok((token_id, events))
+            } else {
+                Err(Error::custom(codes::CANNOT_INCREASE_LAST_TOKEN_ID))
+            }
+        }
+
+        /// Get the id the next sequential mint uses, jumping over reserved ranges and ids claimed from released reservations
+        fn _next_sequential_id(&self) -> Option<u64> {
+            let mut id = self.manager_psp34_standard.last_token_id.checked_add(1)?;
+            loop {
+                id = self.manager_psp34_standard.skip_reserved(id)?;
+                if self.data.owner_of(&Id::U64(id)).is_none() {
+                    return Some(id);
+                }
+                id = id.checked_add(1)?;
+            }
+        }
+
+        /// Mints `token_id` to `to`, leaving the events to the caller
+        fn _mint_id_without_event(
+            &mut self,
+            to: AccountId,
+            token_id: Id,
+        ) -> Result<Vec<PSP34Event>, Error> {
+            self.manager_psp34_standard.check_not_frozen(&[to])?;
+            self._check_transfer_registry(self.env().account_id(), to)?;
+            if !self.manager_psp34_standard.allow_remint_burned
+                && self
+                    .manager_psp34_standard
+                    .burned_tokens
+                    .contains(&token_id)
+            {
+                return Err(Error::TokenWasBurned);
+            }
+            let events = self.data.mint(to, token_id.clone())?;
+            self.manager_psp34_standard
+                .mint_time
+                .insert(&token_id, &self.env().block_timestamp());
+            self.manager_psp34_standard.apply_mint_template(&token_id);
+            self._notify_mint(to, &token_id)?;
+            Ok(events)
+        }
+
+        /// This function let NFT Contract Owner to mint the NFT `id` to `to`, for example to migrate tokens from another chain. When `id` is a `U64` above the last token id, sequential mints continue after it
+        #[ink(message)]
+        pub fn mint_with_id(&mut self, to: AccountId, id: Id) -> Result<(), Error> {
+            self._check_owner_mint(self.env().caller())?;
+            self._check_bare_mint()?;
+            self._mint_explicit(to, id)
+        }
+
+        /// This function let NFT Contract Owner to require every owner mint to come with NFT Traits/Attributes. While it is on, `mint`, `mint_and_approve`, `mint_with_id`, `mint_batch` and `mint_batch_compact` are rejected, and `mint_with_attributes` and `import_tokens` need a non-empty metadata list
+        #[ink(message)]
+        pub fn set_require_attributes_on_mint(&mut self, required: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.require_attributes_on_mint = required;
+            Ok(())
+        }
+
+        /// This function check if owner mints must come with NFT Traits/Attributes
+        #[ink(message)]
+        pub fn is_attributes_required_on_mint(&self) -> bool {
+            self.manager_psp34_standard.require_attributes_on_mint
+        }
+
+        /// This function let NFT Contract Owner to reserve the NFTs `start` to `end` (inclusive) for `for_account`, for example for a partner. Sequential mints skip reserved ids and only `for_account` can mint them with `claim_reserved`. After `deadline` the Contract Owner can release the ids that were not claimed with `release_reservation`
+        #[ink(message)]
+        pub fn reserve_id_range(
+            &mut self,
+            start: u64,
+            end: u64,
+            for_account: AccountId,
+            deadline: Timestamp,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .reserve_id_range(start, end, for_account, deadline)
+        }
+
+        /// This function let the account an id range is reserved for mint one of its ids
+        #[ink(message)]
+        pub fn claim_reserved(&mut self, id: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let token_id = Id::U64(id);
+            if self.manager_psp34_standard.reservation_of(id).is_none() {
+                return Err(Error::custom(codes::ID_IS_NOT_RESERVED));
+            }
+            self.manager_psp34_standard
+                .check_not_reserved(&token_id, Some(caller))?;
+            self.manager_psp34_standard.check_mintable_id(&token_id)?;
+            let events = self._mint_id_without_event(caller, token_id)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to release the reservation starting at `start` once its deadline has passed. Its unclaimed ids become available to sequential mints
+        #[ink(message)]
+        pub fn release_reservation(&mut self, start: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .release_reservation(start, self.env().block_timestamp())
+        }
+
+        /// This function returns the reserved id ranges sorted by their first id
+        #[ink(message)]
+        pub fn get_reservations(&self) -> Vec<IdReservation> {
+            self.manager_psp34_standard.reservations.clone()
+        }
+
+        /// This function returns the reservation containing an id, if any
+        #[ink(message)]
+        pub fn reservation_of(&self, id: u64) -> Option<IdReservation> {
+            self.manager_psp34_standard.reservation_of(id).cloned()
+        }
+
+        /// This function let NFT Contract Owner to recreate up to `MAX_BATCH_SIZE` tokens of an existing collection per call, minting each id to its owner with its attributes. Imports are only possible until `finalize_import` is called
+        #[ink(message)]
+        pub fn import_tokens(&mut self, entries: Vec<ImportEntry>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if self.manager_psp34_standard.import_finalized {
+                return Err(Error::custom(codes::IMPORT_IS_FINALIZED));
+            }
+            if entries.len() > MAX_BATCH_SIZE {
+                return Err(Error::custom(codes::BATCH_SIZE_EXCEEDED));
+            }
+            for (id, owner, metadata) in entries {
+                if metadata.is_empty() {
+                    self._check_bare_mint()?;
+                }
+                self._mint_explicit(owner, id.clone())?;
+                self.manager_psp34_standard
+                    .set_attributes_unchecked_for_new_token(&id, metadata)?;
+            }
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to permanently end the import of an existing collection
+        #[ink(message)]
+        pub fn finalize_import(&mut self) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.import_finalized = true;
+            Ok(())
+        }
+
+        /// This function check if the import of an existing collection has been finalized
+        #[ink(message)]
+        pub fn is_import_finalized(&self) -> bool {
+            self.manager_psp34_standard.import_finalized
+        }
+
+        /// Mints the explicit token id `id` to `to`, moving `last_token_id` past it when needed
+        fn _mint_explicit(&mut self, to: AccountId, id: Id) -> Result<(), Error> {
+            self.manager_psp34_standard.check_mintable_id(&id)?;
+            self.manager_psp34_standard.check_not_reserved(&id, None)?;
+            let events = self._mint_id_without_event(to, id.clone())?;
+            if let Id::U64(n) = id {
+                if n > self.manager_psp34_standard.last_token_id {
+                    self.manager_psp34_standard.last_token_id = n;
+                }
+            }
+            self.emit_events(events);
+            self._emit_sold_out();
+            Ok(())
+        }
+
+        /// This function lets anyone redeem a voucher signed by the voucher signer to mint the NFT `Id::U64(token_id)` to `to`. The signature is an ECDSA signature of the Blake2x256 hash of the SCALE encoded `(contract, to, token_id)`, and each token id can only be redeemed once
+        #[ink(message)]
+        pub fn redeem_voucher(
+            &mut self,
+            to: AccountId,
+            token_id: u64,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            self._verify_voucher(to, token_id, &signature)?;
+            self.manager_psp34_standard
+                .redeemed_vouchers
+                .insert(token_id, &());
+            self._mint_explicit(to, Id::U64(token_id))
+        }
+
+        /// This function let NFT Contract Owner to set the compressed ECDSA public key that signs vouchers, or None to stop redemptions
+        #[ink(message)]
+        pub fn set_voucher_signer(&mut self, public_key: Option<[u8; 33]>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            let signer = public_key.map(|public_key| Self::_account_of(&public_key));
+            self.manager_psp34_standard.voucher_signer = signer;
+            self.env().emit_event(SignerChanged { signer });
+            Ok(())
+        }
+
+        /// This function returns the account of the voucher signer key, if any
+        #[ink(message)]
+        pub fn get_voucher_signer(&self) -> Option<AccountId> {
+            self.manager_psp34_standard.voucher_signer
+        }
+
+        /// This function check if `redeem_voucher` would accept the voucher, without minting
+        #[ink(message)]
+        pub fn verify_voucher(&self, to: AccountId, token_id: u64, signature: [u8; 65]) -> bool {
+            self._verify_voucher(to, token_id, &signature).is_ok()
+        }
+
+        /// Check the voucher for `token_id` has not been redeemed and `signature` was made by the voucher signer
+        fn _verify_voucher(
+            &self,
+            to: AccountId,
+            token_id: u64,
+            signature: &[u8; 65],
+        ) -> Result<(), Error> {
+            let signer = self
+                .manager_psp34_standard
+                .voucher_signer
+                .ok_or(Error::custom(codes::VOUCHER_SIGNER_IS_NOT_SET))?;
+            if self
+                .manager_psp34_standard
+                .redeemed_vouchers
+                .contains(token_id)
+            {
+                return Err(Error::custom(codes::VOUCHER_ALREADY_REDEEMED));
+            }
+            let public_key =
+                self._recover_signer(&(self.env().account_id(), to, token_id), signature)?;
+            if Self::_account_of(&public_key) != signer {
+                return Err(Error::custom(codes::INVALID_VOUCHER_SIGNATURE));
+            }
+            Ok(())
+        }
+
+        /// Recover the compressed ECDSA public key that signed the Blake2x256 hash of the SCALE encoded `payload`
+        fn _recover_signer<T: scale::Encode>(
+            &self,
+            payload: &T,
+            signature: &[u8; 65],
+        ) -> Result<[u8; 33], Error> {
+            let mut message_hash = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_encoded::<Blake2x256, _>(payload, &mut message_hash);
+            self.env()
+                .ecdsa_recover(signature, &message_hash)
+                .map_err(|_| Error::custom(codes::INVALID_SIGNATURE))
+        }
+
+        /// The account of a compressed ECDSA public key
+        fn _account_of(public_key: &[u8; 33]) -> AccountId {
+            let mut account = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Blake2x256>(public_key, &mut account);
+            AccountId::from(account)
+        }
+
+        /// This function let NFT Contract Owner to set the compressed ECDSA public key of the oracle allowed to update attributes through `submit_signed_attributes`, or None to remove it
+        #[ink(message)]
+        pub fn set_attribute_oracle(&mut self, public_key: Option<[u8; 33]>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.attribute_oracle =
+                public_key.map(|public_key| Self::_account_of(&public_key));
+            Ok(())
+        }
+
+        /// This function returns the account of the attribute oracle key, if any
+        #[ink(message)]
+        pub fn get_attribute_oracle(&self) -> Option<AccountId> {
+            self.manager_psp34_standard.attribute_oracle
+        }
+
+        /// This function returns the nonce the next signed attribute update of an NFT must use
+        #[ink(message)]
+        pub fn get_oracle_nonce(&self, token_id: Id) -> u64 {
+            self.manager_psp34_standard
+                .oracle_nonces
+                .get(&token_id)
+                .unwrap_or(0)
+        }
+
+        /// This function lets anyone relay attributes signed by the attribute oracle. The signature is an ECDSA signature of the Blake2x256 hash of the SCALE encoded `(contract, token_id, metadata, nonce)`, and `nonce` must be `get_oracle_nonce(token_id)`. Locked NFTs and attributes of a namespace whose writer is not the oracle account are rejected
+        #[ink(message)]
+        pub fn submit_signed_attributes(
+            &mut self,
+            token_id: Id,
+            metadata: Vec<(String, String)>,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            let oracle = self
+                .manager_psp34_standard
+                .attribute_oracle
+                .ok_or(Error::custom(codes::ATTRIBUTE_ORACLE_IS_NOT_SET))?;
+            if nonce != self.get_oracle_nonce(token_id.clone()) {
+                return Err(Error::custom(codes::INVALID_NONCE));
+            }
+            let public_key = self._recover_signer(
+                &(self.env().account_id(), &token_id, &metadata, nonce),
+                &signature,
+            )?;
+            if Self::_account_of(&public_key) != oracle {
+                return Err(Error::custom(codes::INVALID_SIGNATURE));
+            }
+            for (attribute, _) in &metadata {
+                self.manager_psp34_standard
+                    .check_attribute_writer(attribute, oracle, true)?;
+            }
+            let next_nonce = nonce
+                .checked_add(1)
+                .ok_or(Error::custom(codes::CANNOT_INCREASE_NONCE))?;
+            self.manager_psp34_standard
+                .oracle_nonces
+                .insert(&token_id, &next_nonce);
+            self.manager_psp34_standard
+                .set_multiple_attributes(token_id, metadata)
+        }
+
+        /// This function lets a relayer execute `call` on behalf of `call.from`, who pays no fee. The signature is an ECDSA signature of the Blake2x256 hash of the SCALE encoded `(contract, call)` by the key of `call.from`. The nonce must be `get_meta_nonce(call.from)` and the call is rejected after `call.deadline`
+        #[ink(message)]
+        pub fn execute_meta_tx(
+            &mut self,
+            call: MetaCall,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            if self.env().block_timestamp() > call.deadline {
+                return Err(Error::custom(codes::META_TRANSACTION_EXPIRED));
+            }
+            if call.nonce != self.get_meta_nonce(call.from) {
+                return Err(Error::custom(codes::INVALID_NONCE));
+            }
+            let public_key = self._recover_signer(&(self.env().account_id(), &call), &signature)?;
+            if Self::_account_of(&public_key) != call.from {
+                return Err(Error::custom(codes::INVALID_SIGNATURE));
+            }
+            let next_nonce = call
+                .nonce
+                .checked_add(1)
+                .ok_or(Error::custom(codes::CANNOT_INCREASE_NONCE))?;
+            self.manager_psp34_standard
+                .meta_nonces
+                .insert(call.from, &next_nonce);
+            match call.payload {
+                MetaPayload::Transfer { to, id } => {
+                    self._check_marketplace(call.from, &id)?;
+                    self._transfer(call.from, to, id, Vec::new())?;
+                }
+                MetaPayload::Approve {
+                    operator,
+                    id,
+                    approved,
+                } => self._approve(call.from, operator, id, approved)?,
+                MetaPayload::Lock { id } => self._lock(call.from, id)?,
+            }
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to make an NFT owner approving itself for that NFT succeed instead of failing with `SelfApprove`. Nothing is recorded and no event is emitted since an owner can always move its NFTs, so `allowance` still returns false for the owner itself
+        #[ink(message)]
+        pub fn set_allow_self_approval(&mut self, allowed: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.allow_self_approval = allowed;
+            Ok(())
+        }
+
+        /// This function check if NFT owners can approve themselves without an error
+        #[ink(message)]
+        pub fn is_self_approval_allowed(&self) -> bool {
+            self.manager_psp34_standard.allow_self_approval
+        }
+
+        /// This function let NFT Contract Owner to set the contract allowed to act on behalf of other accounts through the `*_for_sender` messages, or None to remove it
+        #[ink(message)]
+        pub fn set_trusted_forwarder(&mut self, account: Option<AccountId>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.trusted_forwarder = account;
+            Ok(())
+        }
+
+        /// This function returns the trusted forwarder, if any
+        #[ink(message)]
+        pub fn get_trusted_forwarder(&self) -> Option<AccountId> {
+            self.manager_psp34_standard.trusted_forwarder
+        }
+
+        /// This function let the trusted forwarder transfer an NFT as `sender`, with the same checks as `transfer`
+        #[ink(message)]
+        pub fn transfer_for_sender(
+            &mut self,
+            sender: AccountId,
+            to: AccountId,
+            id: Id,
+            data: Vec<u8>,
+        ) -> Result<(), Error> {
+            let sender = self._effective_caller(sender)?;
+            self._check_marketplace(sender, &id)?;
+            self._transfer(sender, to, id, data)?;
+            Ok(())
+        }
+
+        /// This function let the trusted forwarder approve an operator as `sender`, with the same checks as `approve`
+        #[ink(message)]
+        pub fn approve_for_sender(
+            &mut self,
+            sender: AccountId,
+            operator: AccountId,
+            id: Option<Id>,
+            approved: bool,
+        ) -> Result<(), Error> {
+            let sender = self._effective_caller(sender)?;
+            self._approve(sender, operator, id, approved)?;
+            Ok(())
+        }
+
+        /// This function let the trusted forwarder lock an NFT as `sender`, with the same checks as `lock`
+        #[ink(message)]
+        pub fn lock_for_sender(&mut self, sender: AccountId, token_id: Id) -> Result<(), Error> {
+            let sender = self._effective_caller(sender)?;
+            self._lock(sender, token_id)
+        }
+
+        /// This function let the trusted forwarder burn an NFT as `sender`, with the same checks as `burn`
+        #[ink(message)]
+        pub fn burn_for_sender(
+            &mut self,
+            sender: AccountId,
+            account: AccountId,
+            id: Id,
+        ) -> Result<(), Error> {
+            let sender = self._effective_caller(sender)?;
+            self._burn(sender, account, id)?;
+            Ok(())
+        }
+
+        /// Returns `sender` when the caller is the trusted forwarder, emitting `Forwarded`
+        fn _effective_caller(&mut self, sender: AccountId) -> Result<AccountId, Error> {
+            let forwarder = self.env().caller();
+            if self.manager_psp34_standard.trusted_forwarder != Some(forwarder) {
+                return Err(Error::custom(codes::CALLER_IS_NOT_THE_TRUSTED_FORWARDER));
+            }
+            self.env().emit_event(Forwarded { forwarder, sender });
+            Ok(sender)
+        }
+
+        /// This function returns the nonce the next meta transaction of `account` must use
+        #[ink(message)]
+        pub fn get_meta_nonce(&self, account: AccountId) -> u64 {
+            self.manager_psp34_standard
+                .meta_nonces
+                .get(account)
+                .unwrap_or(0)
+        }
+
+        /// Call `PSP34Receiver::on_mint_received` on `to` when it is a contract and mint notifications are enabled
+        fn _notify_mint(&self, to: AccountId, id: &Id) -> Result<(), Error> {
+            if !self.manager_psp34_standard.notify_on_mint || !self.env().is_contract(&to) {
+                return Ok(());
+            }
+            let accepted = build_call::<DefaultEnvironment>()
+                .call(to)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP34Receiver::on_mint_received"
+                    )))
+                    .push_arg(self.env().caller())
+                    .push_arg(id),
+                )
+                .returns::<Result<(), PSP34Error>>()
+                .try_invoke();
+            match accepted {
+                Ok(Ok(Ok(()))) => Ok(()),
+                _ => Err(Error::PSP34Error(PSP34Error::SafeTransferCheckFailed(
+                    String::from("Recipient rejected the minted token"),
+                ))),
+            }
+        }
+
+        /// This function let NFT Contract Owner to mint `count` new NFTs at once, emitting a `Transfer` event for each of them
+        #[ink(message)]
+        pub fn mint_batch(&mut self, count: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self._check_owner_mint(caller)?;
+            self._check_bare_mint()?;
+            if count == 0 || count > MAX_MINT_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+            for _ in 0..count {
+                self._mint_next(caller)?;
+            }
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to mint `count` new NFTs at once, emitting a single `BatchMinted` event describing the range of ids instead of one `Transfer` event per NFT
+        #[ink(message)]
+        pub fn mint_batch_compact(&mut self, count: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self._check_owner_mint(caller)?;
+            self._check_bare_mint()?;
+            if count == 0 || count > MAX_MINT_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+            let from_id = self.manager_psp34_standard.last_token_id.saturating_add(1);
+            for _ in 0..count {
+                self._mint_next_without_event(caller)?;
+            }
+            self.env().emit_event(BatchMinted {
+                to: caller,
+                from_id,
+                to_id: self.manager_psp34_standard.last_token_id,
+            });
+            self._emit_sold_out();
+            Ok(())
+        }
+
+        /// This function returns when an NFT was minted
+        #[ink(message)]
+        pub fn get_mint_time(&self, token_id: Id) -> Option<Timestamp> {
+            self.manager_psp34_standard.mint_time.get(&token_id)
+        }
+
+        /// This function returns the NFTs minted between `from_ts` and `to_ts` (inclusive). It is a paginated helper for indexers: only the `count` tokens starting at `start_index` in the enumeration of all tokens are checked, so clients must page through `total_supply` to cover the whole collection
+        #[cfg(not(feature = "enumerable"))]
+        #[ink(message)]
+        pub fn tokens_minted_between(
+            &self,
+            from_ts: Timestamp,
+            to_ts: Timestamp,
+            start_index: u128,
+            count: u128,
+        ) -> Vec<Id> {
+            let end_index = start_index
+                .saturating_add(count)
+                .min(self.data.total_supply());
+            (start_index..end_index)
+                .filter_map(|index| self.data.token_by_index(index).ok())
+                .filter(|token_id| {
+                    self.manager_psp34_standard
+                        .mint_time
+                        .get(token_id)
+                        .is_some_and(|minted_at| from_ts <= minted_at && minted_at <= to_ts)
+                })
+                .collect()
+        }
+
+        /// This function returns the NFTs of the caller at indices `start` to `start + count - 1` of its enumeration, at most `MAX_BATCH_SIZE` per call
+        #[cfg(not(feature = "enumerable"))]
+        #[ink(message)]
+        pub fn my_tokens(&self, start: u128, count: u128) -> Vec<Id> {
+            let caller = self.env().caller();
+            let end_index = start
+                .saturating_add(count.min(MAX_BATCH_SIZE as u128))
+                .min(self.data.balance_of(caller) as u128);
+            (start..end_index)
+                .filter_map(|index| self.data.owners_token_by_index(caller, index).ok())
+                .collect()
+        }
+
+        /// This function returns the index of `id` among the NFTs of `owner`, as used by `owners_token_by_index`, or None if `owner` does not own it. The index changes when another NFT of `owner` is transferred or burned
+        #[cfg(not(feature = "enumerable"))]
+        #[ink(message)]
+        pub fn owner_token_index(&self, owner: AccountId, id: Id) -> Option<u128> {
+            self.data.owners_token_index(owner, &id)
+        }
+
+        /// This function returns the owner of the NFTs at indices `start_index` to `start_index + limit - 1` of the enumeration of all tokens, at most `MAX_BATCH_SIZE` per call. The order is stable as long as no NFT is minted or burned, so a full table should be read within one block
+        #[cfg(not(feature = "enumerable"))]
+        #[ink(message)]
+        pub fn export_owners(&self, start_index: u128, limit: u128) -> Vec<(Id, AccountId)> {
+            let end_index = start_index
+                .saturating_add(limit.min(MAX_BATCH_SIZE as u128))
+                .min(self.data.total_supply());
+            (start_index..end_index)
+                .filter_map(|index| self.data.token_by_index(index).ok())
+                .filter_map(|token_id| {
+                    let owner = self.data.owner_of(&token_id)?;
+                    Some((token_id, owner))
+                })
+                .collect()
+        }
+
+        /// This function returns the locked NFTs among the indices `start` to `start + limit - 1` of the enumeration of all tokens, checking at most `MAX_BATCH_SIZE` indices per call, in the same order as `export_owners`
+        #[cfg(not(feature = "enumerable"))]
+        #[ink(message)]
+        pub fn export_locked(&self, start: u128, limit: u128) -> Vec<Id> {
+            let end_index = start
+                .saturating_add(limit.min(MAX_BATCH_SIZE as u128))
+                .min(self.data.total_supply());
+            (start..end_index)
+                .filter_map(|index| self.data.token_by_index(index).ok())
+                .filter(|token_id| self.manager_psp34_standard.is_locked_nft(token_id.clone()))
+                .collect()
+        }
+
+        /// This function returns how many NFTs have each value of the attribute `key`, in order of first appearance. It is a paginated scan, not a running counter: only the `count` tokens starting at `start` in the enumeration of all tokens are counted, so the result is exact for that page and clients must add up the pages through `total_supply` to get the collection-wide distribution
+        #[cfg(not(feature = "enumerable"))]
+        #[ink(message)]
+        pub fn attribute_distribution(
+            &self,
+            key: String,
+            start: u128,
+            count: u128,
+        ) -> Vec<(String, u32)> {
+            let key = key.into_bytes();
+            let end_index = start.saturating_add(count).min(self.data.total_supply());
+            let mut distribution: Vec<(String, u32)> = Vec::new();
+            for token_id in
+                (start..end_index).filter_map(|index| self.data.token_by_index(index).ok())
+            {
+                let value = match self
+                    .manager_psp34_standard
+                    .get_attribute(token_id, key.clone())
+                    .and_then(|value_in_bytes| String::from_utf8(value_in_bytes).ok())
+                {
+                    Some(value) => value,
+                    None => continue,
+                };
+                match distribution.iter_mut().find(|(known, _)| *known == value) {
+                    Some((_, total)) => *total = total.saturating_add(1),
+                    None => distribution.push((value, 1)),
+                }
+            }
+            distribution
+        }
+
+        /// This function lets anyone mint `count` new NFTs while the public mint is enabled, paying the public mint price for each of them
+        #[ink(message, payable)]
+        pub fn public_mint(&mut self, count: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self._public_mint_to(caller, count)?;
+            Ok(())
+        }
+
+        /// This function lets anyone pay the public mint price for `count` new NFTs minted to `recipient`. The NFTs count toward the wallet limit of `recipient`, not the caller
+        #[ink(message, payable)]
+        pub fn mint_for(&mut self, recipient: AccountId, count: u64) -> Result<(), Error> {
+            if recipient == AccountId::from([0u8; 32]) {
+                return Err(Error::InvalidInput);
+            }
+            let ids = self._public_mint_to(recipient, count)?;
+            self.env().emit_event(SponsoredMint {
+                sponsor: self.env().caller(),
+                recipient,
+                ids,
+            });
+            Ok(())
+        }
+
+        fn _public_mint_to(&mut self, recipient: AccountId, count: u64) -> Result<Vec<Id>, Error> {
+            if count == 0 || count > MAX_MINT_BATCH_SIZE as u64 {
+                return Err(Error::InvalidInput);
+            }
+            self.manager_psp34_standard.record_public_mint(
+                recipient,
+                count,
+                self.env().transferred_value(),
+            )?;
+            let mut paid = Vec::new();
+            for _ in 0..count {
+                let price = self
+                    .manager_psp34_standard
+                    .unit_price(self.manager_psp34_standard.last_token_id)?;
+                paid.push((self._mint_next(recipient)?, price));
+            }
+            if self.env().caller() == recipient {
+                let now = self.env().block_timestamp();
+                self.manager_psp34_standard
+                    .record_mint_payments(&paid, recipient, now);
+            }
+            self.manager_psp34_standard
+                .record_escrowed_payments(&paid, recipient);
+            Ok(paid.into_iter().map(|(id, _)| id).collect())
+        }
+
+        /// This function let NFT Contract Owner to set the launchpad contract allowed to mint with `launchpad_mint`, or None to remove it
+        #[ink(message)]
+        pub fn set_launchpad(&mut self, contract: Option<AccountId>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.launchpad = contract;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to disable its own mints while a launchpad is set, so that the launchpad is the only way to mint
+        #[ink(message)]
+        pub fn set_launchpad_only_mint(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.launchpad_only_mint = enabled;
+            Ok(())
+        }
+
+        /// This function returns the launchpad contract allowed to mint, if any
+        #[ink(message)]
+        pub fn get_launchpad(&self) -> Option<AccountId> {
+            self.manager_psp34_standard.launchpad
+        }
+
+        /// This function check if owner mints are disabled while a launchpad is set
+        #[ink(message)]
+        pub fn is_launchpad_only_mint(&self) -> bool {
+            self.manager_psp34_standard.launchpad_only_mint
+        }
+
+        /// This function lets the launchpad contract mint `count` new NFTs to `to` and returns their ids
+        #[ink(message)]
+        pub fn launchpad_mint(&mut self, to: AccountId, count: u64) -> Result<Vec<Id>, Error> {
+            if self.manager_psp34_standard.launchpad != Some(self.env().caller()) {
+                return Err(Error::custom(codes::CALLER_IS_NOT_THE_LAUNCHPAD));
+            }
+            if count == 0 || count > MAX_MINT_BATCH_SIZE as u64 {
+                return Err(Error::InvalidInput);
+            }
+            let mut ids = Vec::new();
+            for _ in 0..count {
+                ids.push(self._mint_next(to)?);
+            }
+            Ok(ids)
+        }
+
+        /// This function let NFT Contract Owner to open or close the public mint
+        #[ink(message)]
+        pub fn set_public_mint_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.public_mint_enabled = enabled;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to set the price of one NFT in the public mint
+        #[ink(message)]
+        pub fn set_public_mint_price(&mut self, price: Balance) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.public_mint_price = price;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to price public mints along a bonding curve: the n-th NFT (counting from 0) costs `base + slope * n` with `Linear`, or `base + slope * (n / size)` with `Step(size)`
+        #[ink(message)]
+        pub fn set_bonding_curve(
+            &mut self,
+            base: Balance,
+            slope: Balance,
+            curve: CurveKind,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_bonding_curve(Some(BondingCurve {
+                    base,
+                    slope,
+                    kind: curve,
+                }))
+        }
+
+        /// This function let NFT Contract Owner to go back to the fixed public mint price
+        #[ink(message)]
+        pub fn clear_bonding_curve(&mut self) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.set_bonding_curve(None)
+        }
+
+        /// This function returns the bonding curve used to price public mints, if any
+        #[ink(message)]
+        pub fn get_bonding_curve(&self) -> Option<BondingCurve> {
+            self.manager_psp34_standard.bonding_curve.clone()
+        }
+
+        /// This function returns the price to pay for the next `count` NFTs of the public mint
+        #[ink(message)]
+        pub fn quote_mint(&self, count: u64) -> Result<Balance, Error> {
+            if count > MAX_MINT_BATCH_SIZE as u64 {
+                return Err(Error::InvalidInput);
+            }
+            self.manager_psp34_standard.quote_mint(count)
+        }
+
+        /// This function let NFT Contract Owner to set how many NFTs one wallet can receive through the public mint. 0 means unlimited
+        #[ink(message)]
+        pub fn set_max_per_wallet(&mut self, limit: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.max_per_wallet = limit;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to set how many NFTs one public mint call can mint. 0 means unlimited. Owner mints are not limited
+        #[ink(message)]
+        pub fn set_max_mint_per_tx(&mut self, limit: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.max_mint_per_tx = limit;
+            Ok(())
+        }
+
+        /// This function returns how many NFTs one public mint call can mint. 0 means unlimited
+        #[ink(message)]
+        pub fn get_max_mint_per_tx(&self) -> u64 {
+            self.manager_psp34_standard.max_mint_per_tx
+        }
+
+        /// This function let NFT Contract Owner to allow `account` to mint up to `amount` NFTs at the public mint price while the public mint is closed. 0 removes the account from the whitelist
+        #[ink(message)]
+        pub fn set_whitelist(&mut self, account: AccountId, amount: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.set_whitelist(account, amount)
+        }
+
+        /// This function let NFT Contract Owner to remove up to `MAX_BATCH_SIZE` accounts from the whitelist at once
+        #[ink(message)]
+        pub fn clear_whitelist(&mut self, accounts: Vec<AccountId>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if accounts.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+            for account in accounts {
+                self.manager_psp34_standard.remove_from_whitelist(account);
+            }
+            Ok(())
+        }
+
+        /// This function returns how many NFTs `account` can still mint while the public mint is closed
+        #[ink(message)]
+        pub fn get_whitelist(&self, account: AccountId) -> u64 {
+            self.manager_psp34_standard.get_whitelist(account)
+        }
+
+        /// This function returns how many accounts are on the whitelist
+        #[ink(message)]
+        pub fn whitelist_count(&self) -> u32 {
+            self.manager_psp34_standard.whitelist_count
+        }
+
+        /// This function returns how many NFTs `account` could mint for itself in one `public_mint` call right now, taking into account the supply, the per-call and per-wallet limits and, while the public mint is closed, its whitelist allowance
+        #[ink(message)]
+        pub fn mintable_now(&self, account: AccountId) -> u32 {
+            self.manager_psp34_standard
+                .mintable_now(account, MAX_MINT_BATCH_SIZE as u64) as u32
+        }
+
+        /// This function check if anyone can mint right now through the public mint: it is enabled, the supply is not frozen and the max supply has not been reached
+        #[ink(message)]
+        pub fn is_mint_open(&self) -> bool {
+            self.manager_psp34_standard.public_mint_enabled
+                && self.manager_psp34_standard.check_mintable().is_ok()
+        }
+
+        /// This function check if the public mint is open
+        #[ink(message)]
+        pub fn is_public_mint_enabled(&self) -> bool {
+            self.manager_psp34_standard.public_mint_enabled
+        }
+
+        /// This function returns the price of one NFT in the public mint
+        #[ink(message)]
+        pub fn get_public_mint_price(&self) -> Balance {
+            self.manager_psp34_standard.public_mint_price
+        }
+
+        /// This function returns how many NFTs one wallet can receive through the public mint. 0 means unlimited
+        #[ink(message)]
+        pub fn get_max_per_wallet(&self) -> u64 {
+            self.manager_psp34_standard.max_per_wallet
+        }
+
+        /// This function returns how many NFTs an account has received through the public mint
+        #[ink(message)]
+        pub fn get_minted_by(&self, account: AccountId) -> u64 {
+            self.manager_psp34_standard.get_minted_by(account)
+        }
+
+        /// This function let NFT Contract Owner to withdraw mint proceeds from the contract
+        #[ink(message)]
+        pub fn withdraw_fees(&mut self, value: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ownable._check_owner(Some(caller))?;
+            let refundable = self
+                .manager_psp34_standard
+                .refundable_proceeds(self.env().block_timestamp());
+            let locked = refundable.saturating_add(self.manager_psp34_standard.escrowed_balance);
+            if value.saturating_add(locked) > self.env().balance() {
+                return Err(Error::NotEnoughBalance);
+            }
+            if self.env().transfer(caller, value).is_err() {
+                return Err(Error::WithdrawFeeError);
+            }
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to turn on or off refunds of public mints. Only NFTs minted while refunds are on can be refunded
+        #[ink(message)]
+        pub fn set_refunds_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.refunds_enabled = enabled;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to set how long after a public mint the minter can get a refund. It applies to NFTs minted afterwards
+        #[ink(message)]
+        pub fn set_refund_window(&mut self, duration: Timestamp) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.refund_window = duration;
+            Ok(())
+        }
+
+        /// This function check if refunds of public mints are enabled
+        #[ink(message)]
+        pub fn is_refunds_enabled(&self) -> bool {
+            self.manager_psp34_standard.refunds_enabled
+        }
+
+        /// This function returns how long after a public mint the minter can get a refund
+        #[ink(message)]
+        pub fn get_refund_window(&self) -> Timestamp {
+            self.manager_psp34_standard.refund_window
+        }
+
+        /// This function lets the account that paid for an NFT in the public mint, and still owns it, burn it and get the price back while the refund window is open
+        #[ink(message)]
+        pub fn refund(&mut self, id: Id) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let amount = self.manager_psp34_standard.take_refund(
+                &id,
+                caller,
+                self.env().block_timestamp(),
+            )?;
+            self._burn(caller, caller, id.clone())?;
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::WithdrawFeeError);
+            }
+            self.env().emit_event(Refunded {
+                id,
+                to: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to hold public mint proceeds in escrow until `reveal` is called. If the collection is not revealed by `reveal_deadline`, minters can burn their NFTs with `claim_escrow_refund` to get the price back. It cannot be turned off
+        #[ink(message)]
+        pub fn enable_reveal_escrow(&mut self, reveal_deadline: Timestamp) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            let now = self.env().block_timestamp();
+            self.manager_psp34_standard
+                .enable_reveal_escrow(reveal_deadline, now)
+        }
+
+        /// This function let NFT Contract Owner to reveal the collection by setting its base URI, releasing the escrowed proceeds for `withdraw_fees`
+        #[ink(message)]
+        pub fn reveal(&mut self, base_uri: String) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.set_base_uri(base_uri)?;
+            self.manager_psp34_standard.reveal()
+        }
+
+        /// This function lets the minter of an NFT bought in the public mint burn it and get the price back when the collection was not revealed by the reveal deadline
+        #[ink(message)]
+        pub fn claim_escrow_refund(&mut self, id: Id) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let amount = self.manager_psp34_standard.take_escrow_refund(
+                &id,
+                caller,
+                self.env().block_timestamp(),
+            )?;
+            self._burn(caller, caller, id.clone())?;
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::WithdrawFeeError);
+            }
+            self.env().emit_event(Refunded {
+                id,
+                to: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// This function returns the public mint proceeds held in escrow until the reveal
+        #[ink(message)]
+        pub fn get_escrowed_balance(&self) -> Balance {
+            self.manager_psp34_standard.escrowed_balance
+        }
+
+        /// This function let NFT Contract Owner to set how long, in milliseconds, an NFT must be held after a transfer before it can be transferred again. The first transfer after minting is not restricted and 0 turns the cooldown off
+        #[ink(message)]
+        pub fn set_transfer_cooldown(&mut self, cooldown: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.transfer_cooldown = cooldown;
+            Ok(())
+        }
+
+        /// This function returns the transfer cooldown in milliseconds
+        #[ink(message)]
+        pub fn get_transfer_cooldown(&self) -> u64 {
+            self.manager_psp34_standard.transfer_cooldown
+        }
+
+        /// This function returns how many milliseconds remain before an NFT can be transferred again, 0 if it can be transferred now
+        #[ink(message)]
+        pub fn transfer_cooldown_remaining(&self, id: Id) -> u64 {
+            self.manager_psp34_standard
+                .transfer_cooldown_remaining(&id, self.env().block_timestamp())
+        }
+
+        /// This function returns how many times NFTs changed hands, not counting mints and burns
+        #[ink(message)]
+        pub fn get_total_transfers(&self) -> u64 {
+            self.data.total_transfers()
+        }
+
+        /// This function returns the lifetime proceeds of paid public mints, including funds already withdrawn or refunded
+        #[ink(message)]
+        pub fn get_total_proceeds(&self) -> Balance {
+            self.manager_psp34_standard.total_proceeds
+        }
+
+        /// This function check if the collection has been revealed
+        #[ink(message)]
+        pub fn is_revealed(&self) -> bool {
+            self.manager_psp34_standard.revealed
+        }
+
+        /// This function returns the time after which minters can claim escrow refunds if the collection is still not revealed
+        #[ink(message)]
+        pub fn get_reveal_deadline(&self) -> Timestamp {
+            self.manager_psp34_standard.reveal_deadline
+        }
+
+        /// This function let NFT Contract Owner to allow `account` to mint up to `amount` more NFTs with `mint_with_allowance`. The allowance replaces any previous one and 0 revokes it
+        #[ink(message)]
+        pub fn set_mint_allowance(&mut self, account: AccountId, amount: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_mint_allowance(account, amount);
+            Ok(())
+        }
+
+        /// This function returns how many more NFTs `account` can mint with its allowance
+        #[ink(message)]
+        pub fn get_mint_allowance(&self, account: AccountId) -> u64 {
+            self.manager_psp34_standard.get_mint_allowance(account)
+        }
+
+        /// This function lets an account with a mint allowance mint `count` new NFTs to itself, reducing its remaining allowance
+        #[ink(message)]
+        pub fn mint_with_allowance(&mut self, count: u32) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if count == 0 || count > MAX_MINT_BATCH_SIZE {
+                return Err(Error::InvalidInput);
+            }
+            self.manager_psp34_standard
+                .consume_mint_allowance(caller, count as u64)?;
+            for _ in 0..count {
+                self._mint_next(caller)?;
+            }
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to turn on or off calling `PSP34Receiver::on_mint_received` on contract recipients of new NFTs. When it is on, a mint to a contract that rejects or does not implement the callback is reverted
+        #[ink(message)]
+        pub fn set_notify_on_mint(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.notify_on_mint = enabled;
+            Ok(())
+        }
+
+        /// This function check if contract recipients of new NFTs are notified
+        #[ink(message)]
+        pub fn is_notify_on_mint(&self) -> bool {
+            self.manager_psp34_standard.notify_on_mint
+        }
+
+        /// This function let NFT Contract Owner to set a registry contract that must allow every transfer and mint, or None to remove it. The registry is called as `is_allowed(from, to) -> bool`, with the collection contract as `from` for mints; burns are not checked
+        #[ink(message)]
+        pub fn set_transfer_registry(&mut self, registry: Option<AccountId>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.transfer_registry = registry;
+            Ok(())
+        }
+
+        /// This function returns the registry contract that must allow transfers, if any
+        #[ink(message)]
+        pub fn get_transfer_registry(&self) -> Option<AccountId> {
+            self.manager_psp34_standard.transfer_registry
+        }
+
+        /// This function let NFT Contract Owner to set the PSP34 collection whose holders can claim a free NFT with `claim_for_token`
+        #[ink(message)]
+        pub fn set_claim_source(&mut self, collection: Option<AccountId>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.claim_source = collection;
+            Ok(())
+        }
+
+        /// This function returns the PSP34 collection whose holders can claim a free NFT
+        #[ink(message)]
+        pub fn get_claim_source(&self) -> Option<AccountId> {
+            self.manager_psp34_standard.claim_source
+        }
+
+        /// This function lets the holder of `source_id` in the claim source collection mint one free NFT. Each source NFT can only be used once
+        #[ink(message)]
+        pub fn claim_for_token(&mut self, source_id: Id) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let source = self
+                .manager_psp34_standard
+                .claim_source
+                .ok_or(Error::custom(codes::CLAIM_SOURCE_IS_NOT_SET))?;
+            if self.has_claimed(source_id.clone()) {
+                return Err(Error::custom(codes::SOURCE_TOKEN_ALREADY_CLAIMED));
+            }
+            let source_owner = build_call::<DefaultEnvironment>()
+                .call(source)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP34::owner_of")))
+                        .push_arg(&source_id),
+                )
+                .returns::<Option<AccountId>>()
+                .try_invoke()
+                .map_err(|_| Error::custom(codes::CANNOT_REACH_CLAIM_SOURCE))?
+                .map_err(|_| Error::custom(codes::CANNOT_REACH_CLAIM_SOURCE))?;
+            if source_owner != Some(caller) {
+                return Err(Error::custom(codes::CALLER_DOES_NOT_OWN_THE_SOURCE_TOKEN));
+            }
+            self.manager_psp34_standard
+                .claimed_sources
+                .insert(&source_id, &true);
+            let token_id = self._mint_next(caller)?;
+            self.env().emit_event(Claimed {
+                claimer: caller,
+                source_id,
+                token_id,
+            });
+            Ok(())
+        }
+
+        /// This function check if a source NFT has already been used to claim
+        #[ink(message)]
+        pub fn has_claimed(&self, source_id: Id) -> bool {
+            self.manager_psp34_standard
+                .claimed_sources
+                .get(&source_id)
+                .unwrap_or(false)
+        }
+
+        /// This function returns the id that the next `mint` or `mint_with_attributes` will create. It reflects the sequential minting mode, which is the only minting mode of this contract
+        #[ink(message)]
+        pub fn next_available_id(&self) -> Option<u64> {
+            self.manager_psp34_standard.next_available_id()
+        }
+
+        /// This function let NFT Contract Owner to set the maximum number of NFTs that can be minted. 0 means unlimited
+        #[ink(message)]
+        pub fn set_max_supply(&mut self, max_supply: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.set_max_supply(max_supply)
+        }
+
+        /// This function returns the maximum number of NFTs that can be minted. 0 means unlimited
+        #[ink(message)]
+        pub fn get_max_supply(&self) -> u64 {
+            self.manager_psp34_standard.get_max_supply()
+        }
+
+        /// This function let NFT Contract Owner to permanently stop minting
+        #[ink(message)]
+        pub fn freeze_supply(&mut self) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.freeze_supply()
+        }
+
+        /// This function check if no more NFT can be minted, either because the max supply has been reached or the supply has been frozen
+        #[ink(message)]
+        pub fn is_supply_frozen(&self) -> bool {
+            self.manager_psp34_standard.is_supply_frozen()
+        }
+
+        /// This function let NFT Contract Owner to allow minting again NFT ids that have been burned
+        #[ink(message)]
+        pub fn set_allow_remint_burned(&mut self, allowed: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.allow_remint_burned = allowed;
+            Ok(())
+        }
+
+        /// This function check if burned NFT ids can be minted again
+        #[ink(message)]
+        pub fn is_remint_burned_allowed(&self) -> bool {
+            self.manager_psp34_standard.allow_remint_burned
+        }
+
+        /// This function check if the NFT `id` has been burned
+        #[ink(message)]
+        pub fn is_burned(&self, id: Id) -> bool {
+            self.manager_psp34_standard.burned_tokens.contains(&id)
+        }
+
+        /// This function let NFT Contract Owner to replace the contract logic with the code at `code_hash`, keeping storage and token ownership. The new code must keep the storage layout of this contract: fields can only be appended, never removed, reordered or retyped
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if self.manager_psp34_standard.upgrades_disabled {
+                return Err(Error::custom(codes::UPGRADES_ARE_DISABLED));
+            }
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::custom(codes::CANNOT_SET_CODE_HASH))?;
+            self.env().emit_event(CodeUpgraded { code_hash });
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to permanently disable `set_code`, freezing the contract logic
+        #[ink(message)]
+        pub fn disable_upgrades(&mut self) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.upgrades_disabled = true;
+            Ok(())
+        }
+
+        /// This function check if the contract logic can still be upgraded
+        #[ink(message)]
+        pub fn is_upgradeable(&self) -> bool {
+            !self.manager_psp34_standard.upgrades_disabled
+        }
+
+        /// This function returns which optional behaviours the collection has turned on
+        #[ink(message)]
+        pub fn feature_flags(&self) -> FeatureFlags {
+            let manager = &self.manager_psp34_standard;
+            FeatureFlags {
+                compliance: manager.compliance_enabled,
+                admin_override: manager.is_admin_override_enabled(),
+                admin_transfer: manager.is_admin_transfer_enabled(),
+                supply_frozen: manager.supply_frozen,
+                public_mint: manager.public_mint_enabled,
+                notify_on_mint: manager.notify_on_mint,
+                remint_burned: manager.allow_remint_burned,
+                require_unlock_to_burn: manager.require_unlock_to_burn,
+                emit_transfer_data: manager.emit_transfer_data,
+                escrow_cancel_anytime: manager.escrow_cancel_anytime,
+                auto_sync_royalty: manager.auto_sync_royalty,
+                attribute_history: manager.metadata.is_history_enabled(),
+                refunds: manager.refunds_enabled,
+                reveal_escrow: manager.reveal_escrow_enabled,
+                upgradeable: !manager.upgrades_disabled,
+                enforced_royalties: manager.enforced_royalties,
+                require_attributes_on_mint: manager.require_attributes_on_mint,
+            }
+        }
+
+        /// This function let NFT Contract Owner to set the creator credited for the collection. It is only displayed by marketplaces and gives no control over the contract
+        #[ink(message)]
+        pub fn set_creator(&mut self, creator: Option<AccountId>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.creator = creator;
+            Ok(())
+        }
+
+        /// This function returns the creator credited for the collection, by default the initial owner
+        #[ink(message)]
+        pub fn get_creator(&self) -> Option<AccountId> {
+            self.manager_psp34_standard.creator
+        }
+
+        /// This function let NFT Contract Owner to set how long, in milliseconds, a locked NFT can still be unlocked by its owner with `unlock`. After that the lock is permanent
+        #[ink(message)]
+        pub fn set_lock_grace_period(&mut self, period: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.lock_grace_period = period;
+            Ok(())
+        }
+
+        /// This function returns how long a locked NFT can still be unlocked by its owner
+        #[ink(message)]
+        pub fn get_lock_grace_period(&self) -> u64 {
+            self.manager_psp34_standard.lock_grace_period
+        }
+
+        /// This function lets the owner of an NFT undo its lock during the lock grace period
+        #[ink(message)]
+        pub fn unlock(&mut self, token_id: Id) -> Result<(), Error> {
+            if self.owner_of(token_id.clone()) != Some(self.env().caller()) {
+                return Err(Error::OwnableError(OwnableError::CallerIsNotOwner));
+            }
+            self.manager_psp34_standard
+                .unlock_in_grace(token_id, self.env().block_timestamp())
+        }
+
+        /// This function returns when the lock of an NFT becomes permanent, or None if it is not locked or was locked before lock times were recorded
+        #[ink(message)]
+        pub fn lock_finalized_at(&self, token_id: Id) -> Option<Timestamp> {
+            self.manager_psp34_standard.lock_finalized_at(&token_id)
+        }
+
+        /// This function returns whether each of `ids` is locked, in the same order. Only the first `MAX_BATCH_SIZE` ids are checked
+        #[ink(message)]
+        pub fn are_locked(&self, ids: Vec<Id>) -> Vec<bool> {
+            ids.into_iter()
+                .take(MAX_BATCH_SIZE)
+                .map(|id| self.manager_psp34_standard.is_locked_nft(id))
+                .collect()
+        }
+
+        /// This function cross-checks the ids `Id::U64(start)` to `Id::U64(start + limit - 1)` against the counters and returns the discrepancies found. At most `MAX_AUDIT_RANGE` ids are checked per call: clients continue from `next_start` and add up `locked` over all calls to compare it with `locked_token_count`
+        #[ink(message)]
+        pub fn audit(&self, start: u64, limit: u64) -> AuditReport {
+            let manager = &self.manager_psp34_standard;
+            let end = start.saturating_add(limit.min(MAX_AUDIT_RANGE));
+            let mut report = AuditReport {
+                locked_token_count: manager.get_locked_token_count(),
+                ..Default::default()
+            };
+            for n in start..end {
+                let token_id = Id::U64(n);
+                let locked = manager.is_locked_nft(token_id.clone());
+                report.scanned += 1;
+                match self.data.owner_of(&token_id) {
+                    Some(owner) => {
+                        report.tokens += 1;
+                        if locked {
+                            report.locked += 1;
+                        }
+                        if self.data.balance_of(owner) == 0 {
+                            report.owners_without_balance += 1;
+                        }
+                    }
+                    None if locked => report.orphan_locks += 1,
+                    None => {}
+                }
+                if n >= 1
+                    && n <= manager.get_attribute_count() as u64
+                    && manager.attribute_names.get(n as u32).is_none()
+                {
+                    report.missing_attribute_names += 1;
+                }
+            }
+            let last = manager
+                .get_last_token_id()
+                .max(manager.get_attribute_count() as u64);
+            report.next_start = if end <= last { Some(end) } else { None };
+            report
+        }
+
+        /// This function returns the version of the contract logic
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            VERSION
+        }
+
+        /// This function returns the code hash of the contract logic
+        #[ink(message)]
+        pub fn code_hash(&self) -> Hash {
+            self.env().own_code_hash().unwrap_or_default()
+        }
+
+        /// This function let NFT Contract Owner to set the URI of an NFT, replacing the one built from the base URI, unless its URI is locked
+        #[ink(message)]
+        pub fn set_token_uri(&mut self, token_id: Id, uri: String) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.set_token_uri(token_id, uri)
+        }
+
+        /// This function let NFT Contract Owner to permanently lock the URI of an NFT. Its attributes can still be changed
+        #[ink(message)]
+        pub fn lock_uri(&mut self, token_id: Id) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if self.owner_of(token_id.clone()).is_none() {
+                return Err(Error::PSP34Error(PSP34Error::TokenNotExists));
+            }
+            self.manager_psp34_standard.lock_uri(token_id);
+            Ok(())
+        }
+
+        /// This function check if the URI of an NFT is locked
+        #[ink(message)]
+        pub fn is_uri_locked(&self, token_id: Id) -> bool {
+            self.manager_psp34_standard.is_uri_locked(&token_id)
+        }
+
+        /// This function returns the raw bytes of a collection attribute (stored on `Id::U8(0)`, like name, symbol and baseURI), without UTF-8 conversion
+        #[ink(message)]
+        pub fn get_collection_attribute_raw(&self, key: Vec<u8>) -> Option<Vec<u8>> {
+            self.manager_psp34_standard
+                .metadata
+                .get_attribute(Id::U8(0), key)
+        }
+
+        /// This function returns all registered attribute names in one call. There is no cap on the number of attribute names, so this reads `get_attribute_count` storage entries and is only practical for small registries; larger ones should use `get_attribute_name`
+        #[ink(message)]
+        pub fn get_all_attribute_names(&self) -> Vec<String> {
+            self.manager_psp34_standard.get_all_attribute_names()
+        }
+
+        /// This function return the attribute index using attribute name, or None if the attribute is not registered
+        #[ink(message)]
+        pub fn get_attribute_index(&self, name: String) -> Option<u32> {
+            self.manager_psp34_standard.get_attribute_index(name)
+        }
+
+        /// This function let NFT Contract Owner to set NFT Traits/Attributes even if the NFT is locked. Only available until the admin override is disabled
+        #[ink(message)]
+        pub fn admin_set_attributes(
+            &mut self,
+            token_id: Id,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .admin_set_attributes(token_id, metadata)
+        }
+
+        /// This function let NFT Contract Owner to permanently disable the admin override. Once disabled, locked NFT traits can never be changed again
+        #[ink(message)]
+        pub fn disable_admin_override(&mut self) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.disable_admin_override()
+        }
+
+        /// This function check if the admin override is enabled or not
+        #[ink(message)]
+        pub fn is_admin_override_enabled(&self) -> bool {
+            self.manager_psp34_standard.is_admin_override_enabled()
+        }
+
+        /// This function let NFT Contract Owner to prepare NFT Traits/Attributes without applying them. Staged attributes accumulate until `commit_staged` is called
+        #[ink(message)]
+        pub fn stage_attributes(
+            &mut self,
+            token_id: Id,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .stage_attributes(token_id, metadata)
+        }
+
+        /// This function let NFT Contract Owner to apply the staged attributes of multiple NFTs at once. Reverts if any NFT is locked or has nothing staged
+        #[ink(message)]
+        pub fn commit_staged(&mut self, token_ids: Vec<Id>) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if token_ids.len() > MAX_BATCH_SIZE {
+                return Err(Error::custom(codes::BATCH_SIZE_EXCEEDED));
+            }
+            for token_id in token_ids {
+                self.manager_psp34_standard.commit_staged(token_id)?;
+            }
+            Ok(())
+        }
+
+        /// This function returns how many NFTs have staged attributes waiting for `commit_staged`
+        #[ink(message)]
+        pub fn staged_count(&self) -> u32 {
+            self.manager_psp34_standard.staged_count
+        }
+
+        /// This function check if an NFT has staged attributes waiting for `commit_staged`
+        #[ink(message)]
+        pub fn is_staged(&self, token_id: Id) -> bool {
+            self.manager_psp34_standard.is_staged(&token_id)
+        }
+
+        /// This function let NFT Contract Owner to set NFT Traits/Attributes in a given language, e.g. *b"jp". Locked NFTs cannot be changed in any language
+        #[ink(message)]
+        pub fn set_localized_attributes(
+            &mut self,
+            token_id: Id,
+            lang: [u8; 2],
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_localized_attributes(token_id, lang, metadata)
+        }
+
+        /// This function returns the value of an NFT attribute in a given language, or the default value if it has no translation
+        #[ink(message)]
+        pub fn get_localized_attribute(
+            &self,
+            token_id: Id,
+            lang: [u8; 2],
+            key: String,
+        ) -> Option<String> {
+            self.manager_psp34_standard
+                .get_localized_attribute(token_id, lang, key)
+        }
+
+        /// This function returns every language that has been used for localized attributes
+        #[ink(message)]
+        pub fn get_supported_languages(&self) -> Vec<[u8; 2]> {
+            self.manager_psp34_standard.get_supported_languages()
+        }
+
+        /// This function returns the values of the given NFT attributes like `get_attributes`, but with None for attributes that are not set (or not valid UTF-8) instead of an empty string
+        #[ink(message)]
+        pub fn get_attributes_opt(
+            &self,
+            token_id: Id,
+            attributes: Vec<String>,
+        ) -> Vec<Option<String>> {
+            self.manager_psp34_standard
+                .get_attributes_opt(token_id, attributes)
+        }
+
+        /// This function returns the attributes in `required` that have no value set on the NFT. Useful to audit a collection before reveal
+        #[ink(message)]
+        pub fn missing_attributes(&self, token_id: Id, required: Vec<String>) -> Vec<String> {
+            self.manager_psp34_standard
+                .missing_attributes(token_id, required)
+        }
+
+        /// This function let NFT Contract Owner to turn attribute change history on or off. Turning it off keeps the recorded history
+        #[ink(message)]
+        pub fn set_attribute_history(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .metadata
+                .set_history_enabled(enabled);
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to set how many history entries are kept per attribute. 0 restores the default of 8
+        #[ink(message)]
+        pub fn set_attribute_history_depth(&mut self, depth: u32) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .metadata
+                .set_history_depth(depth);
+            Ok(())
+        }
+
+        /// This function check if attribute change history is being recorded
+        #[ink(message)]
+        pub fn is_attribute_history_enabled(&self) -> bool {
+            self.manager_psp34_standard.metadata.is_history_enabled()
+        }
+
+        /// This function returns the change history of an NFT attribute as (block number, hash of the overwritten value), oldest first
+        #[ink(message)]
+        pub fn get_attribute_history(
+            &self,
+            token_id: Id,
+            key: String,
+        ) -> Vec<(BlockNumber, [u8; 32])> {
+            self.manager_psp34_standard
+                .metadata
+                .get_history(&token_id, &key.into_bytes())
+        }
+
+        /// This function let NFT Contract Owner to move NFTs from their current owners, e.g. for a contract migration. Each move is (from, to, token_id) and the whole batch reverts if any move fails. Only available while admin transfer is enabled
+        #[ink(message)]
+        pub fn admin_transfer_batch(
+            &mut self,
+            moves: Vec<(AccountId, AccountId, Id)>,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if !self.manager_psp34_standard.is_admin_transfer_enabled() {
+                return Err(Error::custom(codes::ADMIN_TRANSFER_IS_DISABLED));
+            }
+            if moves.len() > MAX_BATCH_SIZE {
+                return Err(Error::custom(codes::BATCH_SIZE_EXCEEDED));
+            }
+            for (from, to, id) in moves {
+                if self.data.owner_of(&id) != Some(from) {
+                    return Err(Error::PSP34Error(PSP34Error::NotApproved));
+                }
+                self.manager_psp34_standard.check_not_frozen(&[from, to])?;
+                self._check_transfer_registry(from, to)?;
+                let events = self.data.transfer(from, to, id.clone(), Vec::new())?;
+                self.manager_psp34_standard.clear_holder_attributes(&id);
+                self.emit_events(events);
+            }
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to turn admin transfers on or off, unless they have been permanently disabled
+        #[ink(message)]
+        pub fn set_admin_transfer_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_admin_transfer_enabled(enabled)
+        }
+
+        /// This function let NFT Contract Owner to permanently disable admin transfers, guaranteeing holders that their NFTs can never be moved by the owner
+        #[ink(message)]
+        pub fn disable_admin_transfer(&mut self) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.disable_admin_transfer()
+        }
+
+        /// This function check if admin transfers are enabled or not
+        #[ink(message)]
+        pub fn is_admin_transfer_enabled(&self) -> bool {
+            self.manager_psp34_standard.is_admin_transfer_enabled()
+        }
+
+        /// This function let NFT Contract Owner to create an attribute namespace. Attributes named `prefix:<name>` can then only be set by `writer`
+        #[ink(message)]
+        pub fn create_namespace(&mut self, prefix: String, writer: AccountId) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.create_namespace(prefix, writer)
+        }
+
+        /// This function let NFT Contract Owner to change the writer of an attribute namespace
+        #[ink(message)]
+        pub fn set_namespace_writer(
+            &mut self,
+            prefix: String,
+            writer: AccountId,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_namespace_writer(prefix, writer)
+        }
+
+        /// This function let NFT Contract Owner to allow the owner of each NFT to set attribute `key` on it with `set_holder_attribute`
+        #[ink(message)]
+        pub fn allow_holder_attribute(&mut self, key: String) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_holder_attribute_key(key, true)
+        }
+
+        /// This function let NFT Contract Owner to stop NFT owners from setting attribute `key`. Values already set are kept
+        #[ink(message)]
+        pub fn disallow_holder_attribute(&mut self, key: String) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_holder_attribute_key(key, false)
+        }
+
+        /// This function check if NFT owners may set attribute `key`
+        #[ink(message)]
+        pub fn is_holder_attribute(&self, key: String) -> bool {
+            self.manager_psp34_standard.is_holder_attribute_key(&key)
+        }
+
+        /// This function let the owner of an NFT set one of the attributes allowed with `allow_holder_attribute`, unless the NFT is locked. These attributes are removed when the NFT changes hands, unless `set_keep_holder_attributes` is on
+        #[ink(message)]
+        pub fn set_holder_attribute(
+            &mut self,
+            token_id: Id,
+            key: String,
+            value: String,
+        ) -> Result<(), Error> {
+            if self.data.owner_of(&token_id) != Some(self.env().caller()) {
+                return Err(Error::OwnableError(OwnableError::CallerIsNotOwner));
+            }
+            self.manager_psp34_standard
+                .set_holder_attribute(token_id, key, value)
+        }
+
+        /// This function sets the attribute registered at `attribute_index` (see `get_attribute_name`) on an NFT, with the same checks as `set_multiple_attributes`. Unknown indices are rejected with `IndexOutOfRange`
+        #[ink(message)]
+        pub fn set_attribute_by_index(
+            &mut self,
+            token_id: Id,
+            attribute_index: u32,
+            value: String,
+        ) -> Result<(), Error> {
+            let attribute = self
+                .manager_psp34_standard
+                .try_get_attribute_name(attribute_index)?;
+            self.set_multiple_attributes(token_id, vec![(attribute, value)])
+        }
+
+        /// This function let NFT Contract Owner to give the NFTs `start` to `end` (inclusive) their own base URI, for example when a collection was uploaded in several batches. `token_uri` uses it instead of the collection base URI. Ranges cannot overlap
+        #[ink(message)]
+        pub fn set_base_uri_for_range(
+            &mut self,
+            start: u64,
+            end: u64,
+            uri: String,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_base_uri_for_range(start, end, uri)
+        }
+
+        /// This function let NFT Contract Owner to remove the base URI range starting at `start`
+        #[ink(message)]
+        pub fn remove_uri_range(&mut self, start: u64) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.remove_uri_range(start)
+        }
+
+        /// This function returns the base URI ranges as `(start, end, base_uri)`, sorted by `start`
+        #[ink(message)]
+        pub fn get_uri_ranges(&self) -> Vec<UriRange> {
+            self.manager_psp34_standard.uri_ranges.clone()
+        }
+
+        /// This function returns the metadata location of each of `token_ids` like `token_uri`, in the same order. Only the first `MAX_BATCH_SIZE` ids are used
+        #[ink(message)]
+        pub fn token_uris(&self, token_ids: Vec<u64>) -> Vec<String> {
+            token_ids
+                .into_iter()
+                .take(MAX_BATCH_SIZE)
+                .map(|token_id| self.manager_psp34_standard.token_uri(token_id))
+                .collect()
+        }
+
+        /// This function returns the registered attribute names as `(index, name)` for indices `start` to `start + count - 1`, at most `MAX_BATCH_SIZE` per call. Indices start at 1 and names that are not valid UTF-8 are left out
+        #[ink(message)]
+        pub fn attribute_name_pairs(&self, start: u32, count: u32) -> Vec<(u32, String)> {
+            let end = start.saturating_add(count.min(MAX_BATCH_SIZE as u32)).min(
+                self.manager_psp34_standard
+                    .get_attribute_count()
+                    .saturating_add(1),
+            );
+            (start..end)
+                .filter_map(|index| {
+                    let name = self
+                        .manager_psp34_standard
+                        .try_get_attribute_name(index)
+                        .ok()?;
+                    Some((index, name))
+                })
+                .collect()
+        }
+
+        /// This function returns the metadata location of an NFT like `token_uri`, but fails with `BaseUriNotSet` instead of returning a relative `<token_id>.json` when neither the NFT URI nor the base URI is set
+        #[ink(message)]
+        pub fn try_token_uri(&self, token_id: u64) -> Result<String, Error> {
+            self.manager_psp34_standard.try_token_uri(token_id)
+        }
+
+        /// This function returns the attribute name registered at `index`. Indices start at 1 and go up to `get_attribute_count`: anything else fails with `IndexOutOfRange`, and a name that is not valid UTF-8 with `InvalidUtf8`
+        #[ink(message)]
+        pub fn try_get_attribute_name(&self, index: u32) -> Result<String, Error> {
+            self.manager_psp34_standard.try_get_attribute_name(index)
+        }
+
+        /// This function let NFT Contract Owner to propose new attributes for an NFT. They are only set once the owner of the NFT accepts them with `accept_attribute_change`, and the proposal lapses if the NFT changes hands first
+        #[ink(message)]
+        pub fn propose_attribute_change(
+            &mut self,
+            token_id: Id,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ownable._check_owner(Some(caller))?;
+            let holder = self
+                .data
+                .owner_of(&token_id)
+                .ok_or(Error::PSP34Error(PSP34Error::TokenNotExists))?;
+            for (attribute, _) in &metadata {
+                self.manager_psp34_standard
+                    .check_attribute_writer(attribute, caller, true)?;
+            }
+            self.manager_psp34_standard.propose_attribute_change(
+                token_id.clone(),
+                holder,
+                metadata,
+            )?;
+            self.env().emit_event(AttributeChangeProposed {
+                id: token_id,
+                holder,
+            });
+            Ok(())
+        }
+
+        /// This function let the owner of an NFT accept the attribute change proposed for it. The attributes are set even if the NFT is locked
+        #[ink(message)]
+        pub fn accept_attribute_change(&mut self, token_id: Id) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.data.owner_of(&token_id) != Some(caller) {
+                return Err(Error::OwnableError(OwnableError::CallerIsNotOwner));
+            }
+            self.manager_psp34_standard
+                .accept_attribute_change(token_id.clone(), caller)?;
+            self.env()
+                .emit_event(AttributeChangeAccepted { id: token_id });
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner or the owner of an NFT drop the attribute change proposed for it
+        #[ink(message)]
+        pub fn reject_attribute_change(&mut self, token_id: Id) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let owner = self.data.owner_of(&token_id);
+            if owner != Some(caller) {
+                self.ownable._check_owner(Some(caller))?;
+            }
+            self.manager_psp34_standard
+                .reject_attribute_change(&token_id, owner)?;
+            self.env().emit_event(AttributeChangeRejected {
+                id: token_id,
+                by: caller,
+            });
+            Ok(())
+        }
+
+        /// This function returns the attribute change waiting for the owner of an NFT to accept it, if any
+        #[ink(message)]
+        pub fn get_attribute_proposal(&self, token_id: Id) -> Option<AttributeProposal> {
+            self.manager_psp34_standard
+                .get_attribute_proposal(&token_id, self.data.owner_of(&token_id))
+        }
+
+        /// This function let NFT Contract Owner to keep the attributes set by NFT owners when NFTs change hands, instead of removing them
+        #[ink(message)]
+        pub fn set_keep_holder_attributes(&mut self, keep: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.keep_holder_attributes = keep;
+            Ok(())
+        }
+
+        /// This function check if attributes set by NFT owners are kept when NFTs change hands
+        #[ink(message)]
+        pub fn is_keep_holder_attributes(&self) -> bool {
+            self.manager_psp34_standard.keep_holder_attributes
+        }
+
+        /// This function returns the account allowed to write an attribute namespace
+        #[ink(message)]
+        pub fn get_namespace_writer(&self, prefix: String) -> Option<AccountId> {
+            self.manager_psp34_standard.get_namespace_writer(prefix)
+        }
+
+        /// This function lets an NFT owner hand an NFT to the contract until `to` claims it. `to` must claim before `deadline`, after which the owner can take it back
+        #[ink(message)]
+        pub fn escrow_transfer(
+            &mut self,
+            to: AccountId,
+            id: Id,
+            deadline: Timestamp,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.data.owner_of(&id) != Some(caller) {
+                return Err(Error::PSP34Error(PSP34Error::NotApproved));
+            }
+            if deadline <= self.env().block_timestamp() {
+                return Err(Error::InvalidInput);
+            }
+            if self.manager_psp34_standard.is_token_soulbound(&id) {
+                return Err(Error::custom(codes::TOKEN_IS_SOULBOUND));
+            }
+            self.manager_psp34_standard
+                .check_not_frozen(&[caller, to])?;
+            self._check_transfer_registry(caller, to)?;
+            self.manager_psp34_standard.open_escrow(
+                &id,
+                &Escrow {
+                    seller: caller,
+                    recipient: to,
+                    deadline,
+                },
+            )?;
+            let events =
+                self.data
+                    .transfer(caller, self.env().account_id(), id.clone(), Vec::new())?;
+            self.emit_events(events);
+            self.env().emit_event(EscrowCreated {
+                id,
+                seller: caller,
+                recipient: to,
+                deadline,
+            });
+            Ok(())
+        }
+
+        /// This function lets the recipient of an escrowed NFT claim it before the deadline
+        #[ink(message)]
+        pub fn claim_escrow(&mut self, id: Id) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let escrow = self.manager_psp34_standard.claim_escrow(
+                &id,
+                caller,
+                self.env().block_timestamp(),
+            )?;
+            self.manager_psp34_standard
+                .check_not_frozen(&[escrow.recipient])?;
+            self._check_transfer_registry(self.env().account_id(), escrow.recipient)?;
+            let events = self.data.transfer(
+                self.env().account_id(),
+                escrow.recipient,
+                id.clone(),
+                Vec::new(),
+            )?;
+            self.manager_psp34_standard.clear_holder_attributes(&id);
+            self.emit_events(events);
+            self.env().emit_event(EscrowClaimed {
+                id,
+                recipient: escrow.recipient,
+            });
+            Ok(())
+        }
+
+        /// This function lets the seller of an escrowed NFT take it back after the deadline, or at any time before it is claimed if `escrow_cancel_anytime` is set
+        #[ink(message)]
+        pub fn cancel_escrow(&mut self, id: Id) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let escrow = self.manager_psp34_standard.cancel_escrow(
+                &id,
+                caller,
+                self.env().block_timestamp(),
+            )?;
+            self.manager_psp34_standard
+                .check_not_frozen(&[escrow.seller])?;
+            self._check_transfer_registry(self.env().account_id(), escrow.seller)?;
+            let events = self.data.transfer(
+                self.env().account_id(),
+                escrow.seller,
+                id.clone(),
+                Vec::new(),
+            )?;
+            self.manager_psp34_standard.clear_holder_attributes(&id);
+            self.emit_events(events);
+            self.env().emit_event(EscrowCancelled {
+                id,
+                seller: escrow.seller,
+            });
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to allow sellers to cancel an escrow at any time before it is claimed, instead of only after the deadline
+        #[ink(message)]
+        pub fn set_escrow_cancel_anytime(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.escrow_cancel_anytime = enabled;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to set the attributes every NFT minted from now on starts with. Attributes set on an NFT override the template, and NFTs minted before keep the template they were minted with. An empty template stops applying one
+        #[ink(message)]
+        pub fn set_mint_attribute_template(
+            &mut self,
+            template: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_mint_attribute_template(template)
+        }
+
+        /// This function returns the attributes every newly minted NFT starts with
+        #[ink(message)]
+        pub fn get_mint_attribute_template(&self) -> Vec<(String, String)> {
+            self.manager_psp34_standard.mint_template.clone()
+        }
+
+        /// This function let NFT Contract Owner to define a tier of at most `max_supply` NFTs, or update the name and cap of an existing one. NFTs of a tier expose its name as the `tier` attribute
+        #[ink(message)]
+        pub fn define_tier(
+            &mut self,
+            tier_id: u8,
+            name: String,
+            max_supply: u64,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .define_tier(tier_id, name, max_supply)
+        }
+
+        /// This function let NFT Contract Owner to mint a new NFT of the tier `tier_id`
+        #[ink(message)]
+        pub fn mint_tier(&mut self, tier_id: u8) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self._check_owner_mint(caller)?;
+            self.manager_psp34_standard.take_tier_supply(tier_id, 1)?;
+            let token_id = self._mint_next(caller)?;
+            self.manager_psp34_standard
+                .token_tiers
+                .insert(&token_id, &tier_id);
+            Ok(())
+        }
+
+        /// This function lets anyone mint `count` new NFTs of the tier `tier_id` while the public mint is enabled, paying the public mint price for each of them
+        #[ink(message, payable)]
+        pub fn public_mint_tier(&mut self, tier_id: u8, count: u64) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.manager_psp34_standard
+                .take_tier_supply(tier_id, count)?;
+            for token_id in self._public_mint_to(caller, count)? {
+                self.manager_psp34_standard
+                    .token_tiers
+                    .insert(&token_id, &tier_id);
+            }
+            Ok(())
+        }
+
+        /// This function returns the tier `tier_id`, if it is defined
+        #[ink(message)]
+        pub fn get_tier(&self, tier_id: u8) -> Option<Tier> {
+            self.manager_psp34_standard.tiers.get(tier_id)
+        }
+
+        /// This function returns how many NFTs of the tier `tier_id` have been minted
+        #[ink(message)]
+        pub fn tier_minted(&self, tier_id: u8) -> u64 {
+            self.manager_psp34_standard
+                .tiers
+                .get(tier_id)
+                .map_or(0, |tier| tier.minted)
+        }
+
+        /// This function returns the tier of an NFT, if it belongs to one
+        #[ink(message)]
+        pub fn tier_of(&self, token_id: Id) -> Option<u8> {
+            self.manager_psp34_standard.token_tiers.get(&token_id)
+        }
+
+        /// This function let NFT Contract Owner to create an edition of at most `max_copies` numbered NFTs sharing the attributes in `metadata`. Attributes set on a copy override the shared ones, and `edition_number` returns the copy number
+        #[ink(message)]
+        pub fn create_edition(
+            &mut self,
+            edition_id: u32,
+            max_copies: u32,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .create_edition(edition_id, max_copies, metadata)
+        }
+
+        /// This function let NFT Contract Owner to mint the next numbered copy of an edition to `to`
+        #[ink(message)]
+        pub fn mint_edition(&mut self, edition_id: u32, to: AccountId) -> Result<(), Error> {
+            self._check_owner_mint(self.env().caller())?;
+            let copy_number = self.manager_psp34_standard.next_edition_copy(edition_id)?;
+            let token_id = self._mint_next(to)?;
+            self.manager_psp34_standard
+                .token_editions
+                .insert(&token_id, &(edition_id, copy_number));
+            Ok(())
+        }
+
+        /// This function returns the supply of an edition, if it exists
+        #[ink(message)]
+        pub fn get_edition(&self, edition_id: u32) -> Option<Edition> {
+            self.manager_psp34_standard.editions.get(edition_id)
+        }
+
+        /// This function returns the edition id and copy number of an NFT, if it belongs to an edition
+        #[ink(message)]
+        pub fn get_token_edition(&self, id: Id) -> Option<(u32, u32)> {
+            self.manager_psp34_standard.token_editions.get(&id)
+        }
+
+        /// This function returns the escrow terms of an NFT, if any
+        #[ink(message)]
+        pub fn get_escrow(&self, id: Id) -> Option<Escrow> {
+            self.manager_psp34_standard.escrows.get(&id)
+        }
+
+        /// This function let NFT Contract Owner to set who receives royalties and how much, in basis points of the sale price (10000 = 100%)
+        #[ink(message)]
+        pub fn set_royalty(&mut self, receiver: Option<AccountId>, bps: u16) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.set_royalty(receiver, bps)?;
+            self.env().emit_event(RoyaltyChanged { receiver, bps });
+            Ok(())
+        }
+
+        /// This function returns the royalty receiver and the royalty owed for a sale of `token_id` at `sale_price`, or None if no receiver is set
+        #[ink(message)]
+        pub fn royalty_info(
+            &self,
+            _token_id: Id,
+            sale_price: Balance,
+        ) -> Option<(AccountId, Balance)> {
+            self.manager_psp34_standard.royalty_info(sale_price)
+        }
+
+        /// This function let NFT Contract Owner to make the royalty receiver the current Contract Owner
+        #[ink(message)]
+        pub fn sync_royalty_to_owner(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.ownable._check_owner(Some(caller))?;
+            self._set_royalty_receiver(Some(caller));
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to make the royalty receiver follow ownership transfers automatically
+        #[ink(message)]
+        pub fn set_auto_sync_royalty(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.auto_sync_royalty = enabled;
+            Ok(())
+        }
+
+        /// This function check if the royalty receiver follows ownership transfers
+        #[ink(message)]
+        pub fn is_auto_sync_royalty(&self) -> bool {
+            self.manager_psp34_standard.auto_sync_royalty
+        }
+
+        /// This function let NFT Contract Owner to only allow operator contracts on the allowed marketplace list to transfer NFTs. Owners can still transfer their own NFTs and anyone can use `transfer_with_royalty`
+        #[ink(message)]
+        pub fn set_enforced_royalties(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.enforced_royalties = enabled;
+            Ok(())
+        }
+
+        /// This function check if royalties are enforced
+        #[ink(message)]
+        pub fn is_enforced_royalties(&self) -> bool {
+            self.manager_psp34_standard.enforced_royalties
+        }
+
+        /// This function let NFT Contract Owner to add a marketplace contract to or remove it from the operators allowed to transfer NFTs while royalties are enforced
+        #[ink(message)]
+        pub fn set_allowed_marketplace(
+            &mut self,
+            marketplace: AccountId,
+            allowed: bool,
+        ) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard
+                .set_allowed_marketplace(marketplace, allowed);
+            Ok(())
+        }
+
+        /// This function check if a marketplace contract may transfer NFTs while royalties are enforced
+        #[ink(message)]
+        pub fn is_allowed_marketplace(&self, marketplace: AccountId) -> bool {
+            self.manager_psp34_standard
+                .is_allowed_marketplace(marketplace)
+        }
+
+        /// This function transfers an NFT sold at `sale_price`, forwarding the royalty to the royalty receiver first. The transferred value must be exactly the royalty owed and the caller must own or be approved for the NFT. It is allowed even when the caller is not an allowed marketplace
+        #[ink(message, payable)]
+        pub fn transfer_with_royalty(
+            &mut self,
+            to: AccountId,
+            id: Id,
+            sale_price: Balance,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let paid = self.env().transferred_value();
+            let (receiver, amount) = match self.manager_psp34_standard.royalty_info(sale_price) {
+                Some((receiver, amount)) => (Some(receiver), amount),
+                None => (None, 0),
+            };
+            if paid < amount {
+                return Err(Error::NotEnoughBalance);
+            }
+            if paid > amount {
+                return Err(Error::InvalidInput);
+            }
+            if let Some(receiver) = receiver {
+                if amount > 0 && self.env().transfer(receiver, amount).is_err() {
+                    return Err(Error::WithdrawFeeError);
+                }
+            }
+            self._transfer(caller, to, id.clone(), Vec::new())?;
+            self.env().emit_event(RoyaltyPaid {
+                id,
+                receiver,
+                sale_price,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Reject transfers by operator contracts that are not allowed marketplaces while royalties are enforced
+        fn _check_marketplace(&self, caller: AccountId, id: &Id) -> Result<(), PSP34Error> {
+            if !self.manager_psp34_standard.enforced_royalties
+                || self.data.owner_of(id) == Some(caller)
+                || !self.env().is_contract(&caller)
+                || self.manager_psp34_standard.is_allowed_marketplace(caller)
+            {
+                return Ok(());
+            }
+            Err(PSP34Error::custom(
+                codes::OPERATOR_IS_NOT_AN_ALLOWED_MARKETPLACE,
+            ))
+        }
+
+        fn _set_royalty_receiver(&mut self, receiver: Option<AccountId>) {
+            self.manager_psp34_standard.royalty_receiver = receiver;
+            self.env().emit_event(RoyaltyChanged {
+                receiver,
+                bps: self.manager_psp34_standard.royalty_bps,
+            });
+        }
+
+        /// This function let NFT Contract Owner to require locked NFTs to be unlocked before they can be burned
+        #[ink(message)]
+        pub fn set_require_unlock_to_burn(&mut self, required: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.require_unlock_to_burn = required;
+            Ok(())
+        }
+
+        /// This function check if locked NFTs must be unlocked before they can be burned
+        #[ink(message)]
+        pub fn is_unlock_required_to_burn(&self) -> bool {
+            self.manager_psp34_standard.require_unlock_to_burn
+        }
+
+        /// This function let NFT Contract Owner to allow or forbid burning locked NFTs. It is the inverse of `set_require_unlock_to_burn` and burning locked NFTs is allowed by default
+        #[ink(message)]
+        pub fn set_burn_locked_allowed(&mut self, allowed: bool) -> Result<(), Error> {
+            self.set_require_unlock_to_burn(!allowed)
+        }
+
+        /// This function check if locked NFTs can be burned
+        #[ink(message)]
+        pub fn is_burn_locked_allowed(&self) -> bool {
+            !self.manager_psp34_standard.require_unlock_to_burn
+        }
+
+        /// This function let NFT Contract Owner to emit the `data` payload of transfers as a `TransferData` event
+        #[ink(message)]
+        pub fn set_emit_transfer_data(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.emit_transfer_data = enabled;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to set the longest `data` payload emitted as is. Longer payloads are emitted as their hash. 0 restores the default of 128 bytes
+        #[ink(message)]
+        pub fn set_transfer_data_cap(&mut self, cap: u32) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.transfer_data_cap = cap;
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to set how many attribute names the collection can register. 0 restores the default of 10000. It cannot go below `get_attribute_count`
+        #[ink(message)]
+        pub fn set_max_attributes(&mut self, limit: u32) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.set_max_attributes(limit)
+        }
+
+        /// This function returns how many attribute names the collection can register
+        #[ink(message)]
+        pub fn get_max_attributes(&self) -> u32 {
+            self.manager_psp34_standard.get_max_attributes()
+        }
+
+        /// This function check if transfer `data` payloads are emitted
+        #[ink(message)]
+        pub fn is_emit_transfer_data(&self) -> bool {
+            self.manager_psp34_standard.emit_transfer_data
+        }
+
+        /// This function returns the longest `data` payload emitted as is
+        #[ink(message)]
+        pub fn get_transfer_data_cap(&self) -> u32 {
+            self.manager_psp34_standard.get_transfer_data_cap()
+        }
+
+        /// This function returns how many NFTs `owner` held at the end of block `block`
+        #[ink(message)]
+        pub fn balance_of_at(&self, owner: AccountId, block: BlockNumber) -> u32 {
+            self.data.balance_of_at(owner, block)
+        }
+
+        /// This function lets the owner of an NFT delegate the rights attached to it, such as voting, to `to` without moving the NFT, or remove the delegation with None. Delegations are cleared when the NFT is transferred or burned
+        #[ink(message)]
+        pub fn delegate(&mut self, token_id: Id, to: Option<AccountId>) -> Result<(), Error> {
+            let events = self.data.delegate(self.env().caller(), token_id, to)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        /// This function returns the account an NFT is delegated to, if any
+        #[ink(message)]
+        pub fn delegate_of(&self, token_id: Id) -> Option<AccountId> {
+            self.data.delegate_of(&token_id)
+        }
+
+        /// This function returns how many NFTs are delegated to `account`
+        #[ink(message)]
+        pub fn delegated_count(&self, account: AccountId) -> u32 {
+            self.data.delegated_count(account)
+        }
+
+        /// This function returns how many accounts hold at least one NFT
+        #[ink(message)]
+        pub fn get_holder_count(&self) -> u32 {
+            self.data.holder_count()
+        }
+
+        /// This function returns the holder at `index`, for `index` below `get_holder_count`. When a holder leaves, the last holder takes its index, so clients should page through all holders in one go
+        #[ink(message)]
+        pub fn holder_by_index(&self, index: u32) -> Option<AccountId> {
+            self.data.holder_by_index(index)
+        }
+
+        /// This function let NFT Contract Owner to start a snapshot of the balances of all holders at the current block. The balances are recorded by `snapshot_chunk` calls, which read them as of the snapshot block, so later transfers do not change them
+        #[ink(message)]
+        pub fn take_snapshot(&mut self, snapshot_id: u32) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if self.manager_psp34_standard.snapshots.contains(snapshot_id) {
+                return Err(Error::custom(codes::SNAPSHOT_ALREADY_EXISTS));
+            }
+            self.manager_psp34_standard.snapshots.insert(
+                snapshot_id,
+                &Snapshot {
+                    block: self.env().block_number(),
+                    holder_count: self.data.holder_count(),
+                    cursor: 0,
+                },
+            );
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to record the snapshot balances of the next `limit` holders, up to `MAX_BATCH_SIZE`. Holders are walked through the holder index, whose order changes when holders leave, so chunks should be run soon after `take_snapshot`
+        #[ink(message)]
+        pub fn snapshot_chunk(&mut self, snapshot_id: u32, limit: u32) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            let mut snapshot = self
+                .manager_psp34_standard
+                .snapshots
+                .get(snapshot_id)
+                .ok_or(Error::custom(codes::SNAPSHOT_DOES_NOT_EXIST))?;
+            let end = snapshot
+                .cursor
+                .saturating_add(limit.min(MAX_BATCH_SIZE as u32))
+                .min(snapshot.holder_count);
+            for index in snapshot.cursor..end {
+                if let Some(holder) = self.data.holder_by_index(index) {
+                    let balance = self.data.balance_of_at(holder, snapshot.block);
+                    if balance > 0 {
+                        self.manager_psp34_standard
+                            .snapshot_balances
+                            .insert((snapshot_id, holder), &balance);
+                    }
+                }
+            }
+            snapshot.cursor = end;
+            self.manager_psp34_standard
+                .snapshots
+                .insert(snapshot_id, &snapshot);
+            Ok(())
+        }
+
+        /// This function returns the balance recorded for `account` in a snapshot
+        #[ink(message)]
+        pub fn snapshot_balance_of(&self, snapshot_id: u32, account: AccountId) -> u32 {
+            self.manager_psp34_standard
+                .snapshot_balances
+                .get((snapshot_id, account))
+                .unwrap_or(0)
+        }
+
+        /// This function check if all holders of a snapshot have been recorded
+        #[ink(message)]
+        pub fn is_snapshot_complete(&self, snapshot_id: u32) -> bool {
+            self.manager_psp34_standard
+                .snapshots
+                .get(snapshot_id)
+                .is_some_and(|snapshot| snapshot.cursor >= snapshot.holder_count)
+        }
+
+        /// This function returns the progress of a snapshot, if it exists
+        #[ink(message)]
+        pub fn get_snapshot(&self, snapshot_id: u32) -> Option<Snapshot> {
+            self.manager_psp34_standard.snapshots.get(snapshot_id)
+        }
+
+        /// This function check if an account has ever held an NFT of the collection, even if it holds none today
+        #[ink(message)]
+        pub fn has_ever_held(&self, account: AccountId) -> bool {
+            self.data.has_ever_held(account)
+        }
+
+        /// This function check if `operator` may move at least one of `ids` on behalf of `owner`, either through approval for all NFTs or a per-token approval. Only the first `MAX_BATCH_SIZE` ids are checked
+        #[ink(message)]
+        pub fn is_approved_for_any(
+            &self,
+            owner: AccountId,
+            operator: AccountId,
+            ids: Vec<Id>,
+        ) -> bool {
+            self.data.allowance(owner, operator, None)
+                || ids
+                    .iter()
+                    .take(MAX_BATCH_SIZE)
+                    .any(|id| self.data.allowance(owner, operator, Some(id)))
+        }
+
+        /// This function returns every operator `owner` has approved for all of its NFTs. Per-token approvals are not included
+        #[ink(message)]
+        pub fn operators_of(&self, owner: AccountId) -> Vec<AccountId> {
+            self.data.operators_of(owner)
+        }
+
+        /// This function returns how many operators `owner` has approved for all of its NFTs
+        #[ink(message)]
+        pub fn operator_count(&self, owner: AccountId) -> u32 {
+            self.data.operator_count(owner)
+        }
+
+        /// This function returns the account that granted the current per-token approval of an NFT, or None if the NFT has no per-token approval
+        #[ink(message)]
+        pub fn approval_granter(&self, id: Id) -> Option<AccountId> {
+            self.data.approval_granter(&id)
+        }
+
+        /// This function let NFT Contract Owner to make a single NFT non-transferable (soulbound), or transferable again
+        #[ink(message)]
+        pub fn set_token_soulbound(&mut self, token_id: Id, soulbound: bool) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            if self.data.owner_of(&token_id).is_none() {
+                return Err(Error::PSP34Error(PSP34Error::TokenNotExists));
+            }
+            self.manager_psp34_standard
+                .set_token_soulbound(token_id, soulbound);
+            Ok(())
+        }
+
+        /// This function check if an NFT is soulbound or not
+        #[ink(message)]
+        pub fn is_token_soulbound(&self, token_id: Id) -> bool {
+            self.manager_psp34_standard.is_token_soulbound(&token_id)
+        }
+
+        /// This function let NFT Contract Owner to freeze an account so it can neither send, receive, approve nor burn NFTs. Only available if the collection was created with compliance enabled
+        #[ink(message)]
+        pub fn freeze_account(&mut self, account: AccountId) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.freeze_account(account)?;
+            self.env().emit_event(AccountFrozen { account });
+            Ok(())
+        }
+
+        /// This function let NFT Contract Owner to unfreeze an account
+        #[ink(message)]
+        pub fn unfreeze_account(&mut self, account: AccountId) -> Result<(), Error> {
+            self.ownable._check_owner(Some(self.env().caller()))?;
+            self.manager_psp34_standard.unfreeze_account(account)?;
+            self.env().emit_event(AccountUnfrozen { account });
+            Ok(())
         }
 
-        /// This function let NFT Contract Owner to mint a new NFT without providing NFT Traits/Attributes
+        /// This function check if an account is frozen or not
         #[ink(message)]
-        pub fn mint(&mut self) -> Result<(), Error> {
+        pub fn is_account_frozen(&self, account: AccountId) -> bool {
+            self.manager_psp34_standard.is_account_frozen(account)
+        }
+
+        /// This function check if the collection was created with compliance enabled
+        #[ink(message)]
+        pub fn is_compliance_enabled(&self) -> bool {
+            self.manager_psp34_standard.compliance_enabled
+        }
+
+        /// This function sends multiple NFTs owned by (or approved to) the caller to different recipients. Each entry is (token_id, recipient), every transfer goes through the same checks as `transfer` and the whole batch reverts if any of them fails
+        #[ink(message)]
+        pub fn distribute(&mut self, transfers: Vec<(Id, AccountId)>) -> Result<(), PSP34Error> {
+            if transfers.len() > MAX_BATCH_SIZE {
+                return Err(PSP34Error::custom(codes::BATCH_SIZE_EXCEEDED));
+            }
             let caller = self.env().caller();
-            self.ownable._check_owner(Some(caller))?;
-            if let Some(last_token_id) = self.manager_psp34_standard.last_token_id.checked_add(1) {
-                self.manager_psp34_standard.last_token_id = last_token_id;
-                let events = self
-                    .data
-                    .mint(caller, Id::U64(self.manager_psp34_standard.last_token_id))?;
-                self.emit_events(events);
-                return Ok(());
-            } else {
-                return Err(Error::Custom(String::from("Cannot increase last token id")));
+            for (id, to) in transfers {
+                self._check_marketplace(caller, &id)?;
+                self._transfer(caller, to, id, Vec::new())?;
             }
+            Ok(())
         }
 
-        /// This function let NFT Contract Owner to mint a new NFT with NFT Traits/Attributes
+        /// This function transfers an NFT like `transfer` and clears its per-token approvals, emitting an `Approval` event for each one and then the `Transfer` event. Marketplaces can rely on it to leave no approval behind after a sale
         #[ink(message)]
-        pub fn mint_with_attributes(
+        pub fn transfer_and_clear(
             &mut self,
-            metadata: Vec<(String, String)>,
-        ) -> Result<(), Error> {
+            to: AccountId,
+            id: Id,
+            data: Vec<u8>,
+        ) -> Result<(), PSP34Error> {
             let caller = self.env().caller();
-            self.ownable._check_owner(Some(caller))?;
-            if let Some(last_token_id) = self.manager_psp34_standard.last_token_id.checked_add(1) {
-                self.manager_psp34_standard.last_token_id = last_token_id;
-                let events = self
-                    .data
-                    .mint(caller, Id::U64(self.manager_psp34_standard.last_token_id))?;
-                self.emit_events(events);
-                if self
-                    .set_multiple_attributes(
-                        Id::U64(self.manager_psp34_standard.last_token_id),
-                        metadata,
-                    )
-                    .is_err()
-                {
-                    return Err(Error::Custom(String::from("Cannot set attributes")));
+            self._check_marketplace(caller, &id)?;
+            self._transfer(caller, to, id, data)
+        }
+
+        /// This function burns multiple NFTs of `account`. Every NFT goes through the same checks as `burn` and the whole batch reverts if any of them fails
+        #[ink(message)]
+        pub fn burn_batch(&mut self, account: AccountId, ids: Vec<Id>) -> Result<(), PSP34Error> {
+            if ids.len() > MAX_BATCH_SIZE {
+                return Err(PSP34Error::custom(codes::BATCH_SIZE_EXCEEDED));
+            }
+            let caller = self.env().caller();
+            for id in ids {
+                self._burn(caller, account, id)?;
+            }
+            Ok(())
+        }
+
+        fn _burn(
+            &mut self,
+            caller: AccountId,
+            account: AccountId,
+            id: Id,
+        ) -> Result<(), PSP34Error> {
+            if let Some(token_owner) = self.owner_of(id.clone()) {
+                if token_owner != account {
+                    return Err(PSP34Error::custom(codes::NOT_TOKEN_OWNER));
+                }
+                self._check_not_frozen(&[account])?;
+
+                let allowance = self.allowance(account, caller, Some(id.clone()));
+
+                if caller == account || allowance {
+                    if self.manager_psp34_standard.require_unlock_to_burn
+                        && self.manager_psp34_standard.is_locked_nft(id.clone())
+                    {
+                        return Err(PSP34Error::TokenLocked);
+                    }
+                    let events = self.data.burn(caller, account, id.clone())?;
+                    self._purge_token_state(&id)?;
+                    self.manager_psp34_standard.burned_tokens.insert(&id, &true);
+                    self.emit_events(events);
+                } else {
+                    return Err(PSP34Error::custom(
+                        codes::CALLER_IS_NOT_TOKEN_OWNER_OR_APPROVED,
+                    ));
                 }
-                return Ok(());
             } else {
-                return Err(Error::Custom(String::from("Cannot increase last token id")));
+                return Err(PSP34Error::custom(codes::NO_TOKEN_OWNER_FOUND));
+            }
+            Ok(())
+        }
+
+        fn _transfer(
+            &mut self,
+            caller: AccountId,
+            to: AccountId,
+            id: Id,
+            data: Vec<u8>,
+        ) -> Result<(), PSP34Error> {
+            if let Some(owner) = self.data.owner_of(&id) {
+                self._check_not_frozen(&[owner, to])?;
+                self._check_transfer_registry(owner, to)?;
+            }
+            if self.manager_psp34_standard.is_token_soulbound(&id) {
+                return Err(PSP34Error::custom(codes::TOKEN_IS_SOULBOUND));
+            }
+            let memo = self._transfer_memo(&data);
+            let events = self.data.transfer(caller, to, id.clone(), data)?;
+            self.manager_psp34_standard
+                .record_transfer(&id, self.env().block_timestamp())?;
+            self.manager_psp34_standard.clear_holder_attributes(&id);
+            self.emit_events(events);
+            if let Some((data, hashed)) = memo {
+                self.env().emit_event(TransferData { id, data, hashed });
+            }
+            Ok(())
+        }
+
+        fn _approve(
+            &mut self,
+            caller: AccountId,
+            operator: AccountId,
+            id: Option<Id>,
+            approved: bool,
+        ) -> Result<(), PSP34Error> {
+            self._check_not_frozen(&[caller, operator])?;
+            let id = self._collection_scoped(id);
+            if approved
+                && operator == caller
+                && self.manager_psp34_standard.allow_self_approval
+                && id
+                    .as_ref()
+                    .is_some_and(|id| self.data.owner_of(id) == Some(caller))
+            {
+                return Ok(());
+            }
+            let events = self.data.approve(caller, operator, id, approved)?;
+            self.emit_events(events);
+            Ok(())
+        }
+
+        fn _lock(&mut self, caller: AccountId, token_id: Id) -> Result<(), Error> {
+            if self.data.owner_of(&token_id) != Some(caller) {
+                return Err(Error::OwnableError(OwnableError::CallerIsNotOwner));
+            }
+            self.manager_psp34_standard.lock(token_id.clone())?;
+            self.manager_psp34_standard
+                .lock_times
+                .insert(&token_id, &self.env().block_timestamp());
+            Ok(())
+        }
+
+        /// Ask the transfer registry, if any, whether `from` may send an NFT to `to`. Rejects when it answers false or cannot be called
+        fn _check_transfer_registry(
+            &self,
+            from: AccountId,
+            to: AccountId,
+        ) -> Result<(), PSP34Error> {
+            let registry = match self.manager_psp34_standard.transfer_registry {
+                Some(registry) => registry,
+                None => return Ok(()),
+            };
+            let allowed = build_call::<DefaultEnvironment>()
+                .call(registry)
+                .gas_limit(REGISTRY_GAS_LIMIT)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("is_allowed")))
+                        .push_arg(from)
+                        .push_arg(to),
+                )
+                .returns::<bool>()
+                .try_invoke();
+            match allowed {
+                Ok(Ok(true)) => Ok(()),
+                _ => Err(PSP34Error::custom(codes::TRANSFER_REJECTED_BY_REGISTRY)),
+            }
+        }
+
+        /// Returns the memo to emit for a transfer `data` payload: the payload itself, or its hash when it exceeds the cap
+        fn _transfer_memo(&self, data: &[u8]) -> Option<(Vec<u8>, bool)> {
+            if !self.manager_psp34_standard.emit_transfer_data || data.is_empty() {
+                return None;
+            }
+            if data.len() <= self.manager_psp34_standard.get_transfer_data_cap() as usize {
+                return Some((data.to_vec(), false));
+            }
+            let mut hash = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Blake2x256>(data, &mut hash);
+            Some((hash.to_vec(), true))
+        }
+
+        /// The collection id stands for every token of the owner, so it is treated like `None`
+        fn _collection_scoped(&self, id: Option<Id>) -> Option<Id> {
+            id.filter(|id| *id != self.collection_id())
+        }
+
+        fn _check_not_frozen(&self, accounts: &[AccountId]) -> Result<(), PSP34Error> {
+            self.manager_psp34_standard
+                .check_not_frozen(accounts)
+                .map_err(|_| PSP34Error::custom(codes::ACCOUNT_IS_FROZEN))
+        }
+
+        /// Clears everything stored for a burned token so its storage deposit is refunded
+        fn _purge_token_state(&mut self, id: &Id) -> Result<(), PSP34Error> {
+            if self.manager_psp34_standard.is_locked_nft(id.clone()) {
+                if let Some(locked_token_count) = self
+                    .manager_psp34_standard
+                    .locked_token_count
+                    .checked_sub(1)
+                {
+                    self.manager_psp34_standard.locked_token_count = locked_token_count;
+                } else {
+                    return Err(PSP34Error::custom(codes::LOCKED_TOKEN_COUNT_ERROR));
+                }
             }
+            self.manager_psp34_standard.locked_tokens.remove(id);
+            self.manager_psp34_standard.lock_times.remove(id);
+            self.manager_psp34_standard.metadata.remove_attributes(id);
+            self.manager_psp34_standard.mint_time.remove(id);
+            self.manager_psp34_standard.soulbound_tokens.remove(id);
+            self.manager_psp34_standard.token_editions.remove(id);
+            self.manager_psp34_standard.mint_payments.remove(id);
+            self.manager_psp34_standard.escrowed_payments.remove(id);
+            self.manager_psp34_standard.token_templates.remove(id);
+            self.manager_psp34_standard.token_tiers.remove(id);
+            self.manager_psp34_standard.token_uris.remove(id);
+            self.manager_psp34_standard.uri_locked.remove(id);
+            self.manager_psp34_standard.holder_attributes.remove(id);
+            self.manager_psp34_standard.attribute_proposals.remove(id);
+            self.manager_psp34_standard.last_transfer_at.remove(id);
+            self.manager_psp34_standard.remove_staged(id);
+            Ok(())
         }
 
         fn emit_events(&self, events: ink::prelude::vec::Vec<PSP34Event>) {
@@ -127,6 +2932,9 @@ mod psp34_nft {
                     PSP34Event::AttributeSet { id, key, data } => {
                         self.env().emit_event(AttributeSet { id, key, data })
                     }
+                    PSP34Event::DelegateChanged { id, from, to } => {
+                        self.env().emit_event(DelegateChanged { id, from, to })
+                    }
                 }
             }
         }
@@ -168,6 +2976,166 @@ mod psp34_nft {
         new_owner: Option<AccountId>,
     }
 
+    #[ink(event)]
+    pub struct EscrowCreated {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        seller: AccountId,
+        #[ink(topic)]
+        recipient: AccountId,
+        deadline: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct EscrowClaimed {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        recipient: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct EscrowCancelled {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        seller: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SponsoredMint {
+        #[ink(topic)]
+        sponsor: AccountId,
+        #[ink(topic)]
+        recipient: AccountId,
+        ids: Vec<Id>,
+    }
+
+    #[ink(event)]
+    pub struct Claimed {
+        #[ink(topic)]
+        claimer: AccountId,
+        source_id: Id,
+        token_id: Id,
+    }
+
+    /// Emitted instead of one `Transfer` per token by `mint_batch_compact`, for the ids `from_id..=to_id`
+    #[ink(event)]
+    pub struct BatchMinted {
+        #[ink(topic)]
+        to: AccountId,
+        from_id: u64,
+        to_id: u64,
+    }
+
+    /// Emitted next to `Transfer` when the transfer carries a `data` payload and transfer data emission is on. `data` holds the Blake2x256 hash of the payload when `hashed` is set
+    #[ink(event)]
+    pub struct TransferData {
+        #[ink(topic)]
+        id: Id,
+        data: Vec<u8>,
+        hashed: bool,
+    }
+
+    #[ink(event)]
+    pub struct RoyaltyChanged {
+        #[ink(topic)]
+        receiver: Option<AccountId>,
+        bps: u16,
+    }
+
+    #[ink(event)]
+    pub struct AttributeChangeProposed {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        holder: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AttributeChangeAccepted {
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct AttributeChangeRejected {
+        #[ink(topic)]
+        id: Id,
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Forwarded {
+        #[ink(topic)]
+        forwarder: AccountId,
+        #[ink(topic)]
+        sender: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SoldOut {
+        total_supply: u64,
+    }
+
+    #[ink(event)]
+    pub struct RoyaltyPaid {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        receiver: Option<AccountId>,
+        sale_price: Balance,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AccountFrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AccountUnfrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ContractFinalized {
+        #[ink(topic)]
+        old_owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Refunded {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        id: Id,
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct SignerChanged {
+        signer: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        code_hash: Hash,
+    }
+
     impl PSP34 for Psp34Nft {
         #[ink(message)]
         fn collection_id(&self) -> Id {
@@ -186,6 +3154,7 @@ mod psp34_nft {
 
         #[ink(message)]
         fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            let id = self._collection_scoped(id);
             self.data.allowance(owner, operator, id.as_ref())
         }
 
@@ -196,9 +3165,9 @@ mod psp34_nft {
             id: Id,
             data: ink::prelude::vec::Vec<u8>,
         ) -> Result<(), PSP34Error> {
-            let events = self.data.transfer(self.env().caller(), to, id, data)?;
-            self.emit_events(events);
-            Ok(())
+            let caller = self.env().caller();
+            self._check_marketplace(caller, &id)?;
+            self._transfer(caller, to, id, data)
         }
 
         #[ink(message)]
@@ -208,11 +3177,7 @@ mod psp34_nft {
             id: Option<Id>,
             approved: bool,
         ) -> Result<(), PSP34Error> {
-            let events = self
-                .data
-                .approve(self.env().caller(), operator, id, approved)?;
-            self.emit_events(events);
-            Ok(())
+            self._approve(self.env().caller(), operator, id, approved)
         }
 
         #[ink(message)]
@@ -233,44 +3198,14 @@ mod psp34_nft {
     impl PSP34Burnable for Psp34Nft {
         #[ink(message)]
         fn burn(&mut self, account: AccountId, id: Id) -> Result<(), PSP34Error> {
-            let caller = Self::env().caller();
-
-            if let Some(token_owner) = self.owner_of(id.clone()) {
-                if token_owner != account {
-                    return Err(PSP34Error::Custom(String::from("not token owner")));
-                }
-
-                let allowance = self.allowance(account, caller, Some(id.clone()));
-
-                if caller == account || allowance {
-                    self.manager_psp34_standard.locked_tokens.remove(&id);
-                    if let Some(locked_token_count) = self
-                        .manager_psp34_standard
-                        .locked_token_count
-                        .checked_sub(1)
-                    {
-                        self.manager_psp34_standard.locked_token_count = locked_token_count;
-                        let events = self.data.burn(caller, account, id)?;
-                        self.emit_events(events);
-                    } else {
-                        return Err(PSP34Error::Custom(String::from("Locked token count error")));
-                    }
-                } else {
-                    return Err(PSP34Error::Custom(String::from(
-                        "caller is not token owner or approved",
-                    )));
-                }
-            } else {
-                return Err(PSP34Error::Custom(String::from("No token owner found")));
-            }
-            Ok(())
+            self._burn(Self::env().caller(), account, id)
         }
     }
 
     impl PSP34Metadata for Psp34Nft {
         #[ink(message)]
         fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
-            self.manager_psp34_standard.metadata.get_attribute(id, key)
+            self.manager_psp34_standard.get_attribute(id, key)
         }
     }
 
@@ -293,23 +3228,29 @@ mod psp34_nft {
         }
         #[ink(message)]
         fn renounce_ownership(&mut self) -> Result<(), OwnableError> {
-            self.ownable._check_owner(Some(self.env().caller()))?;
+            let old_owner = self.env().caller();
+            self.ownable._check_owner(Some(old_owner))?;
             self.ownable.renounce_ownership()?;
             self.env().emit_event(OwnershipTransferred {
-                old_owner: Some(self.env().caller()),
+                old_owner: Some(old_owner),
                 new_owner: None,
             });
+            self.env().emit_event(ContractFinalized { old_owner });
 
             Ok(())
         }
         #[ink(message)]
         fn transfer_ownership(&mut self, new_owner: Option<AccountId>) -> Result<(), OwnableError> {
+            let old_owner = self.owner();
             self.ownable._check_owner(Some(self.env().caller()))?;
             self.ownable.transfer_ownership(new_owner)?;
             self.env().emit_event(OwnershipTransferred {
-                old_owner: self.owner(),
+                old_owner,
                 new_owner,
             });
+            if self.manager_psp34_standard.auto_sync_royalty {
+                self._set_royalty_receiver(new_owner);
+            }
 
             Ok(())
         }
@@ -327,7 +3268,12 @@ mod psp34_nft {
             token_id: Id,
             metadata: Vec<(String, String)>,
         ) -> Result<(), Error> {
-            self.ownable._check_owner(Some(self.env().caller()))?;
+            let caller = self.env().caller();
+            let is_owner = self.ownable._check_owner(Some(caller)).is_ok();
+            for (attribute, _) in &metadata {
+                self.manager_psp34_standard
+                    .check_attribute_writer(attribute, caller, is_owner)?;
+            }
             self.manager_psp34_standard
                 .set_multiple_attributes(token_id, metadata)
         }
@@ -354,11 +3300,7 @@ mod psp34_nft {
         }
         #[ink(message)]
         fn lock(&mut self, token_id: Id) -> Result<(), Error> {
-            if self.owner_of(token_id.clone()) != Some(self.env().caller()) {
-                return Err(Error::OwnableError(OwnableError::CallerIsNotOwner));
-            }
-            self.manager_psp34_standard.lock(token_id)?;
-            Ok(())
+            self._lock(self.env().caller(), token_id)
         }
         #[ink(message)]
         fn is_locked_nft(&self, token_id: Id) -> bool {
@@ -370,8 +3312,8 @@ mod psp34_nft {
         }
 
         #[ink(message)]
-        fn get_owner(&self) -> AccountId {
-            self.ownable.owner().unwrap()
+        fn get_owner(&self) -> Option<AccountId> {
+            self.ownable.owner()
         }
     }
 }
@@ -2,12 +2,30 @@ use crate::{
     data::{Id, PSP34Event},
     PSP34Error,
 };
-use ink::{prelude::{vec::Vec, vec}, storage::Mapping};
+use ink::{
+    env::{
+        hash::{Blake2x256, HashOutput},
+        DefaultEnvironment, Environment,
+    },
+    prelude::{vec, vec::Vec},
+    storage::Mapping,
+};
+
+type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+type Attributes = Mapping<(Id, Vec<u8>), Vec<u8>>;
+type AttributeHistory = Mapping<(Id, Vec<u8>), Vec<(BlockNumber, [u8; 32])>>;
+
+/// Number of history entries kept per attribute unless configured otherwise.
+const DEFAULT_HISTORY_DEPTH: u32 = 8;
 
 #[ink::storage_item]
 #[derive(Default, Debug)]
 pub struct Data {
-    attributes: Mapping<(Id, Vec<u8>), Vec<u8>>,
+    attributes: Attributes,
+    token_keys: Mapping<Id, Vec<Vec<u8>>>,
+    history_enabled: bool,
+    history_depth: u32,
+    history: AttributeHistory,
 }
 
 impl Data {
@@ -15,17 +33,112 @@ impl Data {
         self.attributes.get((&id, &key))
     }
 
+    /// Stores an attribute without building an `AttributeSet` event.
+    pub fn insert_attribute(&mut self, id: &Id, key: &Vec<u8>, value: &Vec<u8>) {
+        if self.history_enabled {
+            self._record_history(id, key);
+        }
+        if self.attributes.insert((id, key), value).is_none() {
+            self._index_key(id, key);
+        }
+    }
+
+    /// Stores several attributes of `id`, indexing the new keys with a single write.
+    pub fn insert_attributes(&mut self, id: &Id, attributes: &[(Vec<u8>, Vec<u8>)]) {
+        let mut keys = self.token_keys.get(id).unwrap_or_default();
+        let indexed = keys.len();
+        for (key, value) in attributes {
+            if self.history_enabled {
+                self._record_history(id, key);
+            }
+            if self.attributes.insert((id, key), value).is_none() {
+                keys.push(key.clone());
+            }
+        }
+        if keys.len() != indexed {
+            self.token_keys.insert(id, &keys);
+        }
+    }
+
+    /// Removes every attribute stored for `id`.
+    pub fn remove_attributes(&mut self, id: &Id) {
+        if let Some(keys) = self.token_keys.take(id) {
+            for key in keys {
+                self.attributes.remove((id, &key));
+            }
+        }
+    }
+
+    /// Removes a single attribute of `id`.
+    pub fn remove_attribute(&mut self, id: &Id, key: &Vec<u8>) {
+        if self.attributes.take((id, key)).is_some() {
+            let mut keys = self.token_keys.get(id).unwrap_or_default();
+            keys.retain(|indexed| indexed != key);
+            self.token_keys.insert(id, &keys);
+        }
+    }
+
+    pub fn set_history_enabled(&mut self, enabled: bool) {
+        self.history_enabled = enabled;
+    }
+
+    pub fn is_history_enabled(&self) -> bool {
+        self.history_enabled
+    }
+
+    /// Sets how many entries are kept per attribute. 0 restores the default.
+    pub fn set_history_depth(&mut self, depth: u32) {
+        self.history_depth = depth;
+    }
+
+    pub fn history_depth(&self) -> u32 {
+        if self.history_depth == 0 {
+            DEFAULT_HISTORY_DEPTH
+        } else {
+            self.history_depth
+        }
+    }
+
+    /// Returns `(block_number, hash of the overwritten value)` entries, oldest first.
+    /// The hash is all zeroes when the attribute had no previous value.
+    pub fn get_history(&self, id: &Id, key: &Vec<u8>) -> Vec<(BlockNumber, [u8; 32])> {
+        self.history.get((id, key)).unwrap_or_default()
+    }
+
+    fn _record_history(&mut self, id: &Id, key: &Vec<u8>) {
+        let mut old_value_hash = <Blake2x256 as HashOutput>::Type::default();
+        if let Some(old_value) = self.attributes.get((id, key)) {
+            ink::env::hash_bytes::<Blake2x256>(&old_value, &mut old_value_hash);
+        }
+        let mut history = self.history.get((id, key)).unwrap_or_default();
+        let depth = self.history_depth() as usize;
+        while history.len() >= depth {
+            history.remove(0);
+        }
+        history.push((
+            ink::env::block_number::<DefaultEnvironment>(),
+            old_value_hash,
+        ));
+        self.history.insert((id, key), &history);
+    }
+
+    fn _index_key(&mut self, id: &Id, key: &[u8]) {
+        let mut keys = self.token_keys.get(id).unwrap_or_default();
+        keys.push(key.to_vec());
+        self.token_keys.insert(id, &keys);
+    }
+
     pub fn set_attribute(
         &mut self,
         id: Id,
         key: Vec<u8>,
         value: Vec<u8>,
     ) -> Result<Vec<PSP34Event>, PSP34Error> {
-        self.attributes.insert((&id, &key), &value);
+        self.insert_attribute(&id, &key, &value);
         Ok(vec![PSP34Event::AttributeSet {
             id,
             key,
             data: value,
         }])
     }
-}
\ No newline at end of file
+}
@@ -22,7 +22,7 @@ impl Data {
     }
 
     pub fn transfer_ownership(&mut self, new_owner: Option<AccountId>) -> Result<(), OwnableError> {
-        if new_owner == None {
+        if new_owner.is_none() {
             return Err(OwnableError::NewOwnerIsNotSet);
         }
         self.owner = new_owner;
@@ -1,10 +1,151 @@
 pub mod manager {
-    use crate::{data::Id, metadata, Error};
-    use ink::prelude::string::ToString;
+    use crate::{codes, data::Id, metadata, Error, OwnableError, PSP34Error};
     use ink::{
+        env::{DefaultEnvironment, Environment},
         prelude::{string::String, vec::Vec},
-        storage::Mapping,
+        primitives::AccountId,
+        storage::{Lazy, Mapping},
     };
+
+    type StagedAttributes = Mapping<Id, Vec<(Vec<u8>, Vec<u8>)>>;
+    type EditionAttributes = Mapping<(u32, Vec<u8>), Vec<u8>>;
+    type TemplateAttributes = Mapping<(u32, Vec<u8>), Vec<u8>>;
+    type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+    /// `(start, end, base_uri)` with both ends inclusive
+    pub type UriRange = (u64, u64, String);
+
+    const TOKEN_URI_SUFFIX: &str = ".json";
+    /// Maximum number of attributes in the mint attribute template
+    const MAX_TEMPLATE_SIZE: usize = 16;
+    /// Maximum number of id ranges with their own base URI
+    const MAX_URI_RANGES: usize = 32;
+    /// Maximum number of reserved id ranges
+    const MAX_RESERVATIONS: usize = 32;
+    /// Longest key or value a token owner can write with `set_holder_attribute`
+    const MAX_HOLDER_ATTRIBUTE_LENGTH: usize = 256;
+
+    /// Royalties are expressed in basis points of the sale price
+    const MAX_ROYALTY_BPS: u16 = 10_000;
+    /// Longest transfer `data` emitted as is, unless configured otherwise
+    const DEFAULT_TRANSFER_DATA_CAP: u32 = 128;
+
+    /// Number of attribute names a collection can register unless configured otherwise
+    const DEFAULT_MAX_ATTRIBUTES: u32 = 10_000;
+    /// Localized keys start with a byte that never appears in UTF-8,
+    /// so they cannot collide with keys set through `set_multiple_attributes`
+    const LOCALIZED_KEY_PREFIX: u8 = 0xFF;
+    const MAX_U64_DIGITS: usize = 20;
+
+    /// Writes `value` in decimal into the tail of `buf` without heap allocation
+    pub(crate) fn format_decimal(mut value: u64, buf: &mut [u8; MAX_U64_DIGITS]) -> &str {
+        let mut start = buf.len();
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        core::str::from_utf8(&buf[start..]).unwrap_or_default()
+    }
+
+    /// Attribute synthesized from the copy number of edition tokens
+    const EDITION_NUMBER_KEY: &[u8] = b"edition_number";
+
+    /// Attribute synthesized from the tier name of tier tokens
+    const TIER_KEY: &[u8] = b"tier";
+
+    fn localized_key(lang: [u8; 2], key: &[u8]) -> Vec<u8> {
+        let mut localized = Vec::with_capacity(key.len() + 3);
+        localized.push(LOCALIZED_KEY_PREFIX);
+        localized.extend_from_slice(&lang);
+        localized.extend_from_slice(key);
+        localized
+    }
+
+    #[cfg(feature = "std")]
+    use ink::storage::traits::StorageLayout;
+
+    /// Terms of a token held by the contract until `recipient` claims it
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct Escrow {
+        pub seller: AccountId,
+        pub recipient: AccountId,
+        pub deadline: u64,
+    }
+
+    /// Attribute change proposed by the contract owner, waiting for `holder` to accept it
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct AttributeProposal {
+        pub holder: AccountId,
+        pub metadata: Vec<(String, String)>,
+    }
+
+    /// Price paid for a token during the public mint, refundable to `minter` until `refund_deadline`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct MintPayment {
+        pub minter: AccountId,
+        pub price: u128,
+        pub refund_deadline: u64,
+    }
+
+    /// Shape of a bonding curve: the price rises by `slope` for every token minted, or for every `n` tokens minted with `Step(n)`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub enum CurveKind {
+        Linear,
+        Step(u64),
+    }
+
+    /// Public mint price depending on how many tokens have been minted
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct BondingCurve {
+        pub base: u128,
+        pub slope: u128,
+        pub kind: CurveKind,
+    }
+
+    /// A tier of the collection with its own supply cap
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct Tier {
+        pub name: String,
+        pub max_supply: u64,
+        pub minted: u64,
+    }
+
+    /// Ids `start` to `end` (inclusive) that only `account` can mint. The owner can release them after `deadline`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct IdReservation {
+        pub start: u64,
+        pub end: u64,
+        pub account: AccountId,
+        pub deadline: u64,
+    }
+
+    /// Progress of a holder snapshot taken at `block`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct Snapshot {
+        pub block: BlockNumber,
+        pub holder_count: u32,
+        pub cursor: u32,
+    }
+
+    /// Supply of an edition of numbered copies sharing the same attributes
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct Edition {
+        pub max_copies: u32,
+        pub minted: u32,
+    }
+
     #[ink::storage_item]
     #[derive(Default, Debug)]
     pub struct Manager {
@@ -15,6 +156,94 @@ pub mod manager {
         pub locked_tokens: Mapping<Id, bool>,
         pub locked_token_count: u64,
         pub metadata: metadata::Data,
+        pub admin_override_enabled: Lazy<bool>,
+        pub attribute_index: Mapping<Vec<u8>, u32>,
+        pub max_supply: u64,
+        pub supply_frozen: bool,
+        pub staged: StagedAttributes,
+        pub supported_languages: Vec<[u8; 2]>,
+        pub admin_transfer_enabled: bool,
+        pub admin_transfer_disabled: bool,
+        pub namespace_writers: Mapping<String, AccountId>,
+        pub mint_time: Mapping<Id, u64>,
+        pub escrows: Mapping<Id, Escrow>,
+        pub escrow_cancel_anytime: bool,
+        pub compliance_enabled: bool,
+        pub frozen_accounts: Mapping<AccountId, ()>,
+        pub soulbound_tokens: Mapping<Id, bool>,
+        pub emit_transfer_data: bool,
+        pub transfer_data_cap: u32,
+        pub require_unlock_to_burn: bool,
+        pub royalty_receiver: Option<AccountId>,
+        pub royalty_bps: u16,
+        pub auto_sync_royalty: bool,
+        pub public_mint_enabled: bool,
+        pub public_mint_price: u128,
+        pub max_per_wallet: u64,
+        pub minted_by: Mapping<AccountId, u64>,
+        pub claim_source: Option<AccountId>,
+        pub claimed_sources: Mapping<Id, bool>,
+        pub mint_allowances: Mapping<AccountId, u64>,
+        pub max_mint_per_tx: u64,
+        pub notify_on_mint: bool,
+        pub burned_tokens: Mapping<Id, bool>,
+        pub allow_remint_burned: bool,
+        pub upgrades_disabled: bool,
+        pub editions: Mapping<u32, Edition>,
+        pub edition_attributes: EditionAttributes,
+        pub token_editions: Mapping<Id, (u32, u32)>,
+        pub refunds_enabled: bool,
+        pub refund_window: u64,
+        pub mint_payments: Mapping<Id, MintPayment>,
+        pub refund_reserves: Vec<(u64, u128)>,
+        pub reveal_escrow_enabled: bool,
+        pub revealed: bool,
+        pub reveal_deadline: u64,
+        pub escrowed_balance: u128,
+        pub escrowed_payments: Mapping<Id, MintPayment>,
+        pub whitelist: Mapping<AccountId, u64>,
+        pub whitelist_count: u32,
+        pub bonding_curve: Option<BondingCurve>,
+        pub launchpad: Option<AccountId>,
+        pub launchpad_only_mint: bool,
+        pub mint_template: Vec<(String, String)>,
+        pub mint_template_version: u32,
+        pub last_template_version: u32,
+        pub template_attributes: TemplateAttributes,
+        pub token_templates: Mapping<Id, u32>,
+        pub creator: Option<AccountId>,
+        pub tiers: Mapping<u8, Tier>,
+        pub token_tiers: Mapping<Id, u8>,
+        pub transfer_registry: Option<AccountId>,
+        pub lock_grace_period: u64,
+        pub lock_times: Mapping<Id, u64>,
+        pub redeemed_vouchers: Mapping<u64, ()>,
+        pub voucher_signer: Option<AccountId>,
+        pub attribute_oracle: Option<AccountId>,
+        pub oracle_nonces: Mapping<Id, u64>,
+        pub snapshots: Mapping<u32, Snapshot>,
+        pub snapshot_balances: Mapping<(u32, AccountId), u32>,
+        pub import_finalized: bool,
+        pub token_uris: Mapping<Id, String>,
+        pub uri_locked: Mapping<Id, bool>,
+        pub total_proceeds: u128,
+        pub enforced_royalties: bool,
+        pub allowed_marketplaces: Mapping<AccountId, ()>,
+        pub holder_attribute_keys: Mapping<String, ()>,
+        pub holder_attributes: Mapping<Id, Vec<String>>,
+        pub keep_holder_attributes: bool,
+        pub attribute_proposals: Mapping<Id, AttributeProposal>,
+        pub sold_out: bool,
+        pub max_attributes: u32,
+        pub uri_ranges: Vec<UriRange>,
+        pub transfer_cooldown: u64,
+        pub last_transfer_at: Mapping<Id, u64>,
+        pub reservations: Vec<IdReservation>,
+        pub staged_count: u32,
+        pub meta_nonces: Mapping<AccountId, u64>,
+        pub trusted_forwarder: Option<AccountId>,
+        pub allow_self_approval: bool,
+        pub require_attributes_on_mint: bool,
         _reserved: Option<()>,
     }
 
@@ -25,28 +254,509 @@ pub mod manager {
 
         /// Get Token Count
         pub fn get_last_token_id(&self) -> u64 {
-            return self.last_token_id;
+            self.last_token_id
+        }
+
+        /// Get the id the next sequential mint will use, or None once ids are exhausted
+        pub fn next_available_id(&self) -> Option<u64> {
+            self.last_token_id.checked_add(1)
+        }
+
+        /// Check one more token can be minted
+        pub fn check_mintable(&self) -> Result<(), Error> {
+            if self.supply_frozen {
+                return Err(Error::custom(codes::SUPPLY_IS_FROZEN));
+            }
+            if self.max_supply != 0 && self.last_token_id >= self.max_supply {
+                return Err(Error::custom(codes::MAX_SUPPLY_REACHED));
+            }
+            Ok(())
+        }
+
+        /// Record that the last token id reached the max supply. Returns true only the first time
+        pub fn mark_sold_out(&mut self) -> bool {
+            if self.sold_out || self.max_supply == 0 || self.last_token_id < self.max_supply {
+                return false;
+            }
+            self.sold_out = true;
+            true
+        }
+
+        /// Reserve the ids `start` to `end` for `account`. Only ids above the last token id can be reserved and reservations cannot overlap
+        pub fn reserve_id_range(
+            &mut self,
+            start: u64,
+            end: u64,
+            account: AccountId,
+            deadline: u64,
+        ) -> Result<(), Error> {
+            if start > end || start <= self.last_token_id {
+                return Err(Error::InvalidInput);
+            }
+            if self.max_supply != 0 && end > self.max_supply {
+                return Err(Error::custom(codes::MAX_SUPPLY_REACHED));
+            }
+            if self.reservations.len() >= MAX_RESERVATIONS {
+                return Err(Error::custom(codes::TOO_MANY_RESERVATIONS));
+            }
+            let position = self
+                .reservations
+                .partition_point(|reservation| reservation.start < start);
+            let overlaps_previous = position
+                .checked_sub(1)
+                .and_then(|previous| self.reservations.get(previous))
+                .is_some_and(|reservation| reservation.end >= start);
+            let overlaps_next = self
+                .reservations
+                .get(position)
+                .is_some_and(|reservation| reservation.start <= end);
+            if overlaps_previous || overlaps_next {
+                return Err(Error::custom(codes::RESERVATION_OVERLAPS));
+            }
+            self.reservations.insert(
+                position,
+                IdReservation {
+                    start,
+                    end,
+                    account,
+                    deadline,
+                },
+            );
+            Ok(())
+        }
+
+        /// Remove the reservation starting at `start` once its deadline has passed. Ids already claimed stay minted
+        pub fn release_reservation(&mut self, start: u64, now: u64) -> Result<(), Error> {
+            let (position, reservation) = self
+                .reservations
+                .iter()
+                .enumerate()
+                .find(|(_, reservation)| reservation.start == start)
+                .ok_or(Error::InvalidInput)?;
+            if now < reservation.deadline {
+                return Err(Error::custom(codes::RESERVATION_HAS_NOT_EXPIRED));
+            }
+            self.reservations.remove(position);
+            Ok(())
+        }
+
+        /// Get the reservation containing `id`, if any
+        pub fn reservation_of(&self, id: u64) -> Option<&IdReservation> {
+            let position = self
+                .reservations
+                .partition_point(|reservation| reservation.start <= id);
+            position
+                .checked_sub(1)
+                .and_then(|previous| self.reservations.get(previous))
+                .filter(|reservation| id <= reservation.end)
+        }
+
+        /// Check `id` is not reserved, unless for `account`
+        pub fn check_not_reserved(&self, id: &Id, account: Option<AccountId>) -> Result<(), Error> {
+            if let Id::U64(n) = id {
+                if let Some(reservation) = self.reservation_of(*n) {
+                    if Some(reservation.account) != account {
+                        return Err(Error::custom(codes::ID_IS_RESERVED));
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Get the first id at or after `id` outside of the reserved ranges
+        pub fn skip_reserved(&self, mut id: u64) -> Option<u64> {
+            while let Some(reservation) = self.reservation_of(id) {
+                id = reservation.end.checked_add(1)?;
+            }
+            Some(id)
+        }
+
+        /// Check the explicit token id `id` can be minted
+        pub fn check_mintable_id(&self, id: &Id) -> Result<(), Error> {
+            if self.supply_frozen {
+                return Err(Error::custom(codes::SUPPLY_IS_FROZEN));
+            }
+            if let Id::U64(n) = id {
+                if self.max_supply != 0 && *n > self.max_supply {
+                    return Err(Error::custom(codes::MAX_SUPPLY_REACHED));
+                }
+            }
+            Ok(())
+        }
+
+        /// Change max supply - 0 means unlimited
+        pub fn set_max_supply(&mut self, max_supply: u64) -> Result<(), Error> {
+            if self.supply_frozen {
+                return Err(Error::custom(codes::SUPPLY_IS_FROZEN));
+            }
+            if max_supply != 0 && max_supply < self.last_token_id {
+                return Err(Error::InvalidInput);
+            }
+            self.max_supply = max_supply;
+            Ok(())
+        }
+
+        /// Get Max Supply
+        pub fn get_max_supply(&self) -> u64 {
+            self.max_supply
+        }
+
+        /// Permanently stop minting
+        pub fn freeze_supply(&mut self) -> Result<(), Error> {
+            if self.supply_frozen {
+                return Err(Error::custom(codes::SUPPLY_IS_ALREADY_FROZEN));
+            }
+            self.supply_frozen = true;
+            Ok(())
+        }
+
+        /// Check no more token can ever be minted
+        pub fn is_supply_frozen(&self) -> bool {
+            self.supply_frozen || self.max_supply != 0 && self.last_token_id >= self.max_supply
+        }
+
+        /// Set the bonding curve used to price public mints instead of the fixed price, or None to go back to the fixed price
+        pub fn set_bonding_curve(&mut self, curve: Option<BondingCurve>) -> Result<(), Error> {
+            if let Some(BondingCurve {
+                kind: CurveKind::Step(0),
+                ..
+            }) = curve
+            {
+                return Err(Error::InvalidInput);
+            }
+            self.bonding_curve = curve;
+            Ok(())
+        }
+
+        /// Get the public mint price of the next token once `minted` tokens have been minted
+        pub fn unit_price(&self, minted: u64) -> Result<u128, Error> {
+            let curve = match &self.bonding_curve {
+                Some(curve) => curve,
+                None => return Ok(self.public_mint_price),
+            };
+            let steps = match curve.kind {
+                CurveKind::Linear => minted,
+                CurveKind::Step(size) => minted.checked_div(size).ok_or(Error::InvalidInput)?,
+            };
+            curve
+                .slope
+                .checked_mul(steps as u128)
+                .and_then(|increase| increase.checked_add(curve.base))
+                .ok_or(Error::InvalidInput)
+        }
+
+        /// Get the public mint price of the next `count` tokens
+        pub fn quote_mint(&self, count: u64) -> Result<u128, Error> {
+            let mut total: u128 = 0;
+            for i in 0..count {
+                let minted = self
+                    .last_token_id
+                    .checked_add(i)
+                    .ok_or(Error::InvalidInput)?;
+                total = total
+                    .checked_add(self.unit_price(minted)?)
+                    .ok_or(Error::InvalidInput)?;
+            }
+            Ok(total)
+        }
+
+        /// Check a public mint of `count` tokens to `recipient` paying `paid` is allowed and record it against the recipient's wallet limit
+        pub fn record_public_mint(
+            &mut self,
+            recipient: AccountId,
+            count: u64,
+            paid: u128,
+        ) -> Result<(), Error> {
+            if !self.public_mint_enabled {
+                let allowance = self.get_whitelist(recipient);
+                if allowance == 0 {
+                    return Err(Error::custom(codes::PUBLIC_MINT_IS_NOT_ENABLED));
+                }
+                if count > allowance {
+                    return Err(Error::custom(codes::EXCEEDS_WHITELIST_ALLOWANCE));
+                }
+                self.whitelist.insert(recipient, &(allowance - count));
+            }
+            if self.max_mint_per_tx != 0 && count > self.max_mint_per_tx {
+                return Err(Error::ExceedsTxLimit);
+            }
+            let price = self.quote_mint(count)?;
+            if paid < price {
+                return Err(Error::NotEnoughBalance);
+            }
+            if paid > price {
+                return Err(Error::InvalidInput);
+            }
+            let minted = self
+                .get_minted_by(recipient)
+                .checked_add(count)
+                .ok_or(Error::InvalidInput)?;
+            if self.max_per_wallet != 0 && minted > self.max_per_wallet {
+                return Err(Error::custom(codes::EXCEEDS_WALLET_LIMIT));
+            }
+            self.total_proceeds = self
+                .total_proceeds
+                .checked_add(price)
+                .ok_or(Error::InvalidInput)?;
+            self.minted_by.insert(recipient, &minted);
+            Ok(())
+        }
+
+        /// Set how many tokens `account` may mint while the public mint is closed. 0 removes the entry
+        pub fn set_whitelist(&mut self, account: AccountId, amount: u64) -> Result<(), Error> {
+            let exists = self.whitelist.contains(account);
+            if amount == 0 {
+                self.remove_from_whitelist(account);
+            } else {
+                if !exists {
+                    self.whitelist_count = self
+                        .whitelist_count
+                        .checked_add(1)
+                        .ok_or(Error::custom(codes::WHITELIST_IS_FULL))?;
+                }
+                self.whitelist.insert(account, &amount);
+            }
+            Ok(())
+        }
+
+        /// Remove `account` from the whitelist
+        pub fn remove_from_whitelist(&mut self, account: AccountId) {
+            if self.whitelist.take(account).is_some() {
+                self.whitelist_count = self.whitelist_count.saturating_sub(1);
+            }
+        }
+
+        /// Get how many tokens `account` may still mint while the public mint is closed
+        pub fn get_whitelist(&self, account: AccountId) -> u64 {
+            self.whitelist.get(account).unwrap_or(0)
+        }
+
+        /// Set how many more tokens `account` may mint with its allowance. 0 revokes the allowance
+        pub fn set_mint_allowance(&mut self, account: AccountId, amount: u64) {
+            if amount == 0 {
+                self.mint_allowances.remove(account);
+            } else {
+                self.mint_allowances.insert(account, &amount);
+            }
+        }
+
+        /// Get how many more tokens `account` may mint with its allowance
+        pub fn get_mint_allowance(&self, account: AccountId) -> u64 {
+            self.mint_allowances.get(account).unwrap_or(0)
+        }
+
+        /// Consume `count` tokens of the mint allowance of `account`
+        pub fn consume_mint_allowance(
+            &mut self,
+            account: AccountId,
+            count: u64,
+        ) -> Result<(), Error> {
+            let remaining = self
+                .get_mint_allowance(account)
+                .checked_sub(count)
+                .ok_or(Error::MintAllowanceExceeded)?;
+            self.set_mint_allowance(account, remaining);
+            Ok(())
+        }
+
+        /// Record that `minter` paid `price` for each `(id, price)` of `paid` at `now`, keeping the proceeds until the refund window closes
+        pub fn record_mint_payments(&mut self, paid: &[(Id, u128)], minter: AccountId, now: u64) {
+            if !self.refunds_enabled {
+                return;
+            }
+            let refund_deadline = now.saturating_add(self.refund_window);
+            let mut total: u128 = 0;
+            for (id, price) in paid.iter().filter(|(_, price)| *price > 0) {
+                self.mint_payments.insert(
+                    id,
+                    &MintPayment {
+                        minter,
+                        price: *price,
+                        refund_deadline,
+                    },
+                );
+                total = total.saturating_add(*price);
+            }
+            if total == 0 {
+                return;
+            }
+            self.refund_reserves.push((refund_deadline, total));
+        }
+
+        /// Consume the refundable payment of `id` by `caller` at `now` and return the amount to refund
+        pub fn take_refund(&mut self, id: &Id, caller: AccountId, now: u64) -> Result<u128, Error> {
+            if !self.refunds_enabled {
+                return Err(Error::custom(codes::REFUNDS_ARE_NOT_ENABLED));
+            }
+            let payment = self
+                .mint_payments
+                .get(id)
+                .ok_or(Error::custom(codes::TOKEN_IS_NOT_REFUNDABLE))?;
+            if payment.minter != caller {
+                return Err(Error::custom(codes::CALLER_IS_NOT_THE_MINTER));
+            }
+            if now > payment.refund_deadline {
+                return Err(Error::custom(codes::REFUND_WINDOW_IS_CLOSED));
+            }
+            self.mint_payments.remove(id);
+            if let Some(escrowed) = self.escrowed_payments.take(id) {
+                self.escrowed_balance = self.escrowed_balance.saturating_sub(escrowed.price);
+            }
+            if let Some(reserve) = self.refund_reserves.iter_mut().find(|(deadline, amount)| {
+                *deadline == payment.refund_deadline && *amount >= payment.price
+            }) {
+                reserve.1 -= payment.price;
+            }
+            Ok(payment.price)
+        }
+
+        /// Hold public mint proceeds in escrow until `reveal`, letting minters get refunds if the collection is not revealed by `reveal_deadline`. It cannot be turned off
+        pub fn enable_reveal_escrow(
+            &mut self,
+            reveal_deadline: u64,
+            now: u64,
+        ) -> Result<(), Error> {
+            if self.reveal_escrow_enabled || self.revealed {
+                return Err(Error::custom(codes::REVEAL_ESCROW_CANNOT_BE_CHANGED));
+            }
+            if reveal_deadline <= now {
+                return Err(Error::InvalidInput);
+            }
+            self.reveal_escrow_enabled = true;
+            self.reveal_deadline = reveal_deadline;
+            Ok(())
+        }
+
+        /// Record that `minter` paid `price` for each `(id, price)` of `paid` into the reveal escrow
+        pub fn record_escrowed_payments(&mut self, paid: &[(Id, u128)], minter: AccountId) {
+            if !self.reveal_escrow_enabled || self.revealed {
+                return;
+            }
+            for (id, price) in paid.iter().filter(|(_, price)| *price > 0) {
+                self.escrowed_payments.insert(
+                    id,
+                    &MintPayment {
+                        minter,
+                        price: *price,
+                        refund_deadline: self.reveal_deadline,
+                    },
+                );
+                self.escrowed_balance = self.escrowed_balance.saturating_add(*price);
+            }
+        }
+
+        /// Mark the collection revealed, releasing the escrowed proceeds
+        pub fn reveal(&mut self) -> Result<(), Error> {
+            if self.revealed {
+                return Err(Error::custom(codes::ALREADY_REVEALED));
+            }
+            self.revealed = true;
+            self.escrowed_balance = 0;
+            Ok(())
+        }
+
+        /// Consume the escrowed payment of `id` by `caller` at `now` and return the amount to refund
+        pub fn take_escrow_refund(
+            &mut self,
+            id: &Id,
+            caller: AccountId,
+            now: u64,
+        ) -> Result<u128, Error> {
+            if self.revealed {
+                return Err(Error::custom(codes::ALREADY_REVEALED));
+            }
+            let payment = self
+                .escrowed_payments
+                .get(id)
+                .ok_or(Error::custom(codes::TOKEN_IS_NOT_REFUNDABLE))?;
+            if payment.minter != caller {
+                return Err(Error::custom(codes::CALLER_IS_NOT_THE_MINTER));
+            }
+            if now <= payment.refund_deadline {
+                return Err(Error::custom(codes::REVEAL_DEADLINE_HAS_NOT_PASSED));
+            }
+            self.escrowed_payments.remove(id);
+            self.escrowed_balance = self.escrowed_balance.saturating_sub(payment.price);
+            Ok(payment.price)
+        }
+
+        /// Drop the reserves whose refund window has closed and return the proceeds still refundable at `now`
+        pub fn refundable_proceeds(&mut self, now: u64) -> u128 {
+            self.refund_reserves
+                .retain(|(deadline, amount)| *deadline >= now && *amount > 0);
+            self.refund_reserves
+                .iter()
+                .fold(0u128, |total, (_, amount)| total.saturating_add(*amount))
+        }
+
+        /// Get how many tokens `account` could mint for itself in one public mint call right now, at most `max_per_call`
+        pub fn mintable_now(&self, account: AccountId, max_per_call: u64) -> u64 {
+            if self.check_mintable().is_err() || self.is_account_frozen(account) {
+                return 0;
+            }
+            let mut mintable = max_per_call;
+            if self.max_mint_per_tx != 0 {
+                mintable = mintable.min(self.max_mint_per_tx);
+            }
+            if self.max_supply != 0 {
+                mintable = mintable.min(self.max_supply.saturating_sub(self.last_token_id));
+            }
+            if !self.public_mint_enabled {
+                return mintable.min(self.get_whitelist(account));
+            }
+            if self.max_per_wallet != 0 {
+                mintable = mintable.min(
+                    self.max_per_wallet
+                        .saturating_sub(self.get_minted_by(account)),
+                );
+            }
+            mintable
+        }
+
+        /// Get how many tokens `account` has received through public mints
+        pub fn get_minted_by(&self, account: AccountId) -> u64 {
+            self.minted_by.get(account).unwrap_or(0)
         }
 
         /// Lock nft - Only owner token
         pub fn lock(&mut self, token_id: Id) -> Result<(), Error> {
+            if self.is_locked_nft(token_id.clone()) {
+                return Err(Error::custom(codes::ALREADY_LOCKED));
+            }
             if let Some(locked_token_count) = self.locked_token_count.checked_add(1) {
                 self.locked_token_count = locked_token_count;
                 self.locked_tokens.insert(&token_id, &true);
-                return Ok(());
+                Ok(())
             } else {
-                return Err(Error::Custom(String::from(
-                    "Cannot increase locked token count",
-                )));
+                Err(Error::custom(codes::CANNOT_INCREASE_LOCKED_TOKEN_COUNT))
             }
         }
 
         /// Check token is locked or not
         pub fn is_locked_nft(&self, token_id: Id) -> bool {
-            if self.locked_tokens.get(&token_id).is_some() {
-                return true;
+            self.locked_tokens.get(&token_id).unwrap_or(false)
+        }
+
+        /// Unlock a token locked less than `lock_grace_period` ago
+        pub fn unlock_in_grace(&mut self, token_id: Id, now: u64) -> Result<(), Error> {
+            if !self.is_locked_nft(token_id.clone()) {
+                return Err(Error::custom(codes::TOKEN_IS_NOT_LOCKED));
             }
-            return false;
+            match self.lock_finalized_at(&token_id) {
+                Some(finalized_at) if now < finalized_at => {}
+                _ => return Err(Error::custom(codes::LOCK_IS_FINAL)),
+            }
+            self.locked_tokens.remove(&token_id);
+            self.lock_times.remove(&token_id);
+            self.locked_token_count = self.locked_token_count.saturating_sub(1);
+            Ok(())
+        }
+
+        /// Get when the lock of a token stops being reversible
+        pub fn lock_finalized_at(&self, token_id: &Id) -> Option<u64> {
+            self.lock_times
+                .get(token_id)
+                .map(|locked_at| locked_at.saturating_add(self.lock_grace_period))
         }
 
         /// Get Locked Token Count
@@ -74,100 +784,940 @@ pub mod manager {
                 return Err(Error::InvalidInput);
             }
             if self.is_locked_nft(token_id.clone()) {
-                return Err(Error::Custom(String::from("Token is locked")));
+                return Err(Error::custom(codes::TOKEN_IS_LOCKED));
             }
-            for (attribute, value) in &metadata {
-                self.add_attribute_name(&attribute.clone().into_bytes())?;
-                self.metadata.set_attribute(
-                    token_id.clone(),
-                    attribute.clone().into_bytes(),
-                    value.clone().into_bytes(),
-                )?;
+            self._set_attributes(token_id, metadata)
+        }
+
+        /// Set multiple attributes to a token that has just been minted.
+        /// A freshly minted token cannot be locked yet, so the lock check is skipped,
+        /// and its attribute keys are indexed with a single write
+        pub fn set_attributes_unchecked_for_new_token(
+            &mut self,
+            token_id: &Id,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            let mut attributes = Vec::with_capacity(metadata.len());
+            for (attribute, value) in metadata {
+                let attribute = attribute.into_bytes();
+                self.add_attribute_name(&attribute)?;
+                attributes.push((attribute, value.into_bytes()));
             }
+            self.metadata.insert_attributes(token_id, &attributes);
             Ok(())
         }
 
-        /// Get multiple  attributes
-        pub fn get_attributes(&self, token_id: Id, attributes: Vec<String>) -> Vec<String> {
-            let length = attributes.len();
-            let mut ret = Vec::<String>::new();
-            for i in 0..length {
-                let attribute = attributes[i].clone();
-                let value = self
-                    .metadata
-                    .get_attribute(token_id.clone(), attribute.into_bytes());
+        /// Only Owner can set multiple attributes to a token, even if it is locked.
+        /// Fails once the admin override has been disabled
+        pub fn admin_set_attributes(
+            &mut self,
+            token_id: Id,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            if !self.is_admin_override_enabled() {
+                return Err(Error::custom(codes::ADMIN_OVERRIDE_IS_DISABLED));
+            }
+            if token_id == Id::U64(0) {
+                return Err(Error::InvalidInput);
+            }
+            self._set_attributes(token_id, metadata)
+        }
 
-                if let Some(value_in_bytes) = value {
-                    if let Ok(value_in_string) = String::from_utf8(value_in_bytes) {
-                        ret.push(value_in_string);
-                    } else {
-                        ret.push(String::from(""));
-                    }
-                } else {
-                    ret.push(String::from(""));
-                }
+        /// Permanently disable the admin override
+        pub fn disable_admin_override(&mut self) -> Result<(), Error> {
+            if !self.is_admin_override_enabled() {
+                return Err(Error::custom(codes::ADMIN_OVERRIDE_IS_ALREADY_DISABLED));
             }
-            ret
+            self.admin_override_enabled.set(&false);
+            Ok(())
         }
 
-        /// Get Attribute Count
-        pub fn get_attribute_count(&self) -> u32 {
-            self.attribute_count
+        /// Turn admin transfers on or off - unless they have been permanently disabled
+        pub fn set_admin_transfer_enabled(&mut self, enabled: bool) -> Result<(), Error> {
+            if self.admin_transfer_disabled {
+                return Err(Error::custom(codes::ADMIN_TRANSFER_IS_PERMANENTLY_DISABLED));
+            }
+            self.admin_transfer_enabled = enabled;
+            Ok(())
         }
-        /// Get Attribute Name
-        pub fn get_attribute_name(&self, index: u32) -> String {
-            let attribute = self.attribute_names.get(&index);
 
-            if let Some(value_in_bytes) = attribute {
-                if let Ok(value_in_string) = String::from_utf8(value_in_bytes) {
-                    return value_in_string;
-                } else {
-                    return String::from("");
-                }
-            } else {
-                return String::from("");
+        /// Permanently disable admin transfers
+        pub fn disable_admin_transfer(&mut self) -> Result<(), Error> {
+            if self.admin_transfer_disabled {
+                return Err(Error::custom(codes::ADMIN_TRANSFER_IS_ALREADY_DISABLED));
             }
+            self.admin_transfer_enabled = false;
+            self.admin_transfer_disabled = true;
+            Ok(())
         }
 
-        /// Get URI from token ID
-        pub fn token_uri(&self, token_id: u64) -> String {
-            let value = self
-                .metadata
-                .get_attribute(Id::U8(0), String::from("baseURI").into_bytes());
-            let mut token_uri = String::from("");
+        /// Check admin transfer is enabled or not
+        pub fn is_admin_transfer_enabled(&self) -> bool {
+            self.admin_transfer_enabled
+        }
 
-            if let Some(value_in_bytes) = value {
-                if let Ok(value_in_string) = String::from_utf8(value_in_bytes) {
-                    token_uri = value_in_string;
+        /// Check admin override is enabled or not
+        pub fn is_admin_override_enabled(&self) -> bool {
+            self.admin_override_enabled.get_or_default()
+        }
+
+        /// Only Owner can stage attributes to a token without applying them
+        pub fn stage_attributes(
+            &mut self,
+            token_id: Id,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            if token_id == Id::U64(0) {
+                return Err(Error::InvalidInput);
+            }
+            if self.is_locked_nft(token_id.clone()) {
+                return Err(Error::custom(codes::TOKEN_IS_LOCKED));
+            }
+            let mut staged = match self.staged.get(&token_id) {
+                Some(staged) => staged,
+                None => {
+                    self.staged_count = self
+                        .staged_count
+                        .checked_add(1)
+                        .ok_or(Error::custom(codes::TOO_MANY_STAGED_TOKENS))?;
+                    Vec::new()
                 }
+            };
+            for (attribute, value) in metadata {
+                staged.push((attribute.into_bytes(), value.into_bytes()));
             }
+            self.staged.insert(&token_id, &staged);
+            Ok(())
+        }
 
-            token_uri = token_uri + &token_id.to_string() + &String::from(".json");
-            token_uri
+        /// Check if a token has staged attributes waiting for `commit_staged`
+        pub fn is_staged(&self, token_id: &Id) -> bool {
+            self.staged.contains(token_id)
         }
 
-        fn add_attribute_name(&mut self, attribute_input: &Vec<u8>) -> Result<(), Error> {
-            if let Ok(attr_input) = String::from_utf8((*attribute_input).clone()) {
-                let exist: bool = self.is_attribute.get(&attr_input).is_some();
-
-                if !exist {
-                    if let Some(attribute_count) = self.attribute_count.checked_add(1) {
-                        self.attribute_count = attribute_count;
-                        self.attribute_names
-                            .insert(&self.attribute_count, attribute_input);
-                        self.is_attribute.insert(&attr_input, &true);
-                        return Ok(());
-                    } else {
-                        return Err(Error::Custom(String::from(
-                            "Fail to increase attribute count",
-                        )));
-                    }
-                } else {
-                    return Err(Error::Custom(String::from("Attribute input exists")));
-                }
+        /// Drop the staged attributes of a token without applying them
+        pub fn remove_staged(&mut self, token_id: &Id) {
+            if self.staged.take(token_id).is_some() {
+                self.staged_count = self.staged_count.saturating_sub(1);
+            }
+        }
+
+        /// Only Owner can apply the staged attributes of a token
+        pub fn commit_staged(&mut self, token_id: Id) -> Result<(), Error> {
+            if self.is_locked_nft(token_id.clone()) {
+                return Err(Error::custom(codes::TOKEN_IS_LOCKED));
+            }
+            let staged = self
+                .staged
+                .take(&token_id)
+                .ok_or(Error::custom(codes::NO_STAGED_ATTRIBUTES))?;
+            self.staged_count = self.staged_count.saturating_sub(1);
+            for (attribute, value) in staged {
+                self.add_attribute_name(&attribute)?;
+                self.metadata
+                    .insert_attribute(&token_id, &attribute, &value);
+            }
+            Ok(())
+        }
+
+        /// Only Owner can create an attribute namespace. Keys starting with `prefix:` can then only be written by `writer`
+        pub fn create_namespace(&mut self, prefix: String, writer: AccountId) -> Result<(), Error> {
+            if prefix.is_empty() || prefix.contains(':') {
+                return Err(Error::InvalidInput);
+            }
+            if self.namespace_writers.contains(&prefix) {
+                return Err(Error::custom(codes::NAMESPACE_EXISTS));
+            }
+            self.namespace_writers.insert(&prefix, &writer);
+            Ok(())
+        }
+
+        /// Only Owner can change the writer of an existing namespace
+        pub fn set_namespace_writer(
+            &mut self,
+            prefix: String,
+            writer: AccountId,
+        ) -> Result<(), Error> {
+            if !self.namespace_writers.contains(&prefix) {
+                return Err(Error::custom(codes::NAMESPACE_NOT_FOUND));
+            }
+            self.namespace_writers.insert(&prefix, &writer);
+            Ok(())
+        }
+
+        /// Get the account allowed to write a namespace
+        pub fn get_namespace_writer(&self, prefix: String) -> Option<AccountId> {
+            self.namespace_writers.get(&prefix)
+        }
+
+        /// Check `caller` may write `attribute`. Namespaced keys belong to the namespace writer, every other key to the contract owner
+        pub fn check_attribute_writer(
+            &self,
+            attribute: &str,
+            caller: AccountId,
+            is_owner: bool,
+        ) -> Result<(), Error> {
+            let writer = attribute
+                .split_once(':')
+                .and_then(|(prefix, _)| self.namespace_writers.get(prefix));
+            match writer {
+                Some(writer) if writer == caller => Ok(()),
+                Some(_) => Err(Error::custom(codes::CALLER_IS_NOT_THE_NAMESPACE_WRITER)),
+                None if is_owner => Ok(()),
+                None => Err(Error::OwnableError(OwnableError::CallerIsNotOwner)),
+            }
+        }
+
+        /// Let token owners write attribute `key` with `set_holder_attribute`, or stop them
+        pub fn set_holder_attribute_key(
+            &mut self,
+            key: String,
+            allowed: bool,
+        ) -> Result<(), Error> {
+            if key.is_empty() || key.len() > MAX_HOLDER_ATTRIBUTE_LENGTH {
+                return Err(Error::InvalidInput);
+            }
+            if allowed {
+                self.holder_attribute_keys.insert(&key, &());
+            } else {
+                self.holder_attribute_keys.remove(&key);
+            }
+            Ok(())
+        }
+
+        /// Check if token owners may write attribute `key`
+        pub fn is_holder_attribute_key(&self, key: &String) -> bool {
+            self.holder_attribute_keys.contains(key)
+        }
+
+        /// Set a holder writable attribute of a token. The caller must be the token owner
+        pub fn set_holder_attribute(
+            &mut self,
+            token_id: Id,
+            key: String,
+            value: String,
+        ) -> Result<(), Error> {
+            if !self.is_holder_attribute_key(&key) {
+                return Err(Error::custom(codes::ATTRIBUTE_IS_NOT_HOLDER_WRITABLE));
+            }
+            if value.len() > MAX_HOLDER_ATTRIBUTE_LENGTH {
+                return Err(Error::InvalidInput);
+            }
+            if self.is_locked_nft(token_id.clone()) {
+                return Err(Error::custom(codes::TOKEN_IS_LOCKED));
+            }
+            let mut keys = self.holder_attributes.get(&token_id).unwrap_or_default();
+            if !keys.contains(&key) {
+                keys.push(key.clone());
+                self.holder_attributes.insert(&token_id, &keys);
+            }
+            let attribute = key.into_bytes();
+            self.add_attribute_name(&attribute)?;
+            self.metadata
+                .insert_attribute(&token_id, &attribute, &value.into_bytes());
+            Ok(())
+        }
+
+        /// Remove the holder writable attributes of a token after it changed hands, unless they are kept on transfer
+        pub fn clear_holder_attributes(&mut self, token_id: &Id) {
+            if self.keep_holder_attributes {
+                return;
+            }
+            if let Some(keys) = self.holder_attributes.get(token_id) {
+                for key in keys {
+                    self.metadata.remove_attribute(token_id, &key.into_bytes());
+                }
+                self.holder_attributes.remove(token_id);
+            }
+        }
+
+        /// Propose new attributes for a token owned by `holder`. A token has at most one pending proposal
+        pub fn propose_attribute_change(
+            &mut self,
+            token_id: Id,
+            holder: AccountId,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            if token_id == Id::U64(0) || metadata.is_empty() {
+                return Err(Error::InvalidInput);
+            }
+            if self
+                .get_attribute_proposal(&token_id, Some(holder))
+                .is_some()
+            {
+                return Err(Error::custom(codes::ATTRIBUTE_CHANGE_ALREADY_PROPOSED));
+            }
+            self.attribute_proposals
+                .insert(&token_id, &AttributeProposal { holder, metadata });
+            Ok(())
+        }
+
+        /// Get the pending proposal of a token. A proposal made to a previous owner is no longer pending
+        pub fn get_attribute_proposal(
+            &self,
+            token_id: &Id,
+            owner: Option<AccountId>,
+        ) -> Option<AttributeProposal> {
+            self.attribute_proposals
+                .get(token_id)
+                .filter(|proposal| Some(proposal.holder) == owner)
+        }
+
+        /// Apply the pending proposal of a token on behalf of its owner, even if the token is locked
+        pub fn accept_attribute_change(
+            &mut self,
+            token_id: Id,
+            owner: AccountId,
+        ) -> Result<(), Error> {
+            let proposal = self
+                .get_attribute_proposal(&token_id, Some(owner))
+                .ok_or(Error::custom(codes::NO_ATTRIBUTE_CHANGE_PROPOSED))?;
+            self.attribute_proposals.remove(&token_id);
+            self._set_attributes(token_id, proposal.metadata)
+        }
+
+        /// Drop the pending proposal of a token
+        pub fn reject_attribute_change(
+            &mut self,
+            token_id: &Id,
+            owner: Option<AccountId>,
+        ) -> Result<(), Error> {
+            if self.get_attribute_proposal(token_id, owner).is_none() {
+                return Err(Error::custom(codes::NO_ATTRIBUTE_CHANGE_PROPOSED));
+            }
+            self.attribute_proposals.remove(token_id);
+            Ok(())
+        }
+
+        /// Change royalty receiver and rate (in basis points)
+        pub fn set_royalty(&mut self, receiver: Option<AccountId>, bps: u16) -> Result<(), Error> {
+            if bps > MAX_ROYALTY_BPS {
+                return Err(Error::InvalidInput);
+            }
+            self.royalty_receiver = receiver;
+            self.royalty_bps = bps;
+            Ok(())
+        }
+
+        /// Get royalty receiver and amount owed for a sale at `sale_price`
+        pub fn royalty_info(&self, sale_price: u128) -> Option<(AccountId, u128)> {
+            let receiver = self.royalty_receiver?;
+            let amount =
+                sale_price.checked_mul(self.royalty_bps as u128)? / MAX_ROYALTY_BPS as u128;
+            Some((receiver, amount))
+        }
+
+        /// Add `marketplace` to or remove it from the operators allowed to transfer while royalties are enforced
+        pub fn set_allowed_marketplace(&mut self, marketplace: AccountId, allowed: bool) {
+            if allowed {
+                self.allowed_marketplaces.insert(marketplace, &());
+            } else {
+                self.allowed_marketplaces.remove(marketplace);
+            }
+        }
+
+        /// Check if `marketplace` may transfer NFTs while royalties are enforced
+        pub fn is_allowed_marketplace(&self, marketplace: AccountId) -> bool {
+            self.allowed_marketplaces.contains(marketplace)
+        }
+
+        /// Change how many attribute names can be registered - 0 restores the default. Cannot go below the names already registered
+        pub fn set_max_attributes(&mut self, limit: u32) -> Result<(), Error> {
+            if limit != 0 && limit < self.attribute_count {
+                return Err(Error::InvalidInput);
+            }
+            self.max_attributes = limit;
+            Ok(())
+        }
+
+        /// Get how many attribute names can be registered
+        pub fn get_max_attributes(&self) -> u32 {
+            if self.max_attributes == 0 {
+                DEFAULT_MAX_ATTRIBUTES
+            } else {
+                self.max_attributes
+            }
+        }
+
+        /// Get how long `token_id` must still be held before it can be transferred again
+        pub fn transfer_cooldown_remaining(&self, token_id: &Id, now: u64) -> u64 {
+            self.last_transfer_at
+                .get(token_id)
+                .map(|last| {
+                    last.saturating_add(self.transfer_cooldown)
+                        .saturating_sub(now)
+                })
+                .unwrap_or(0)
+        }
+
+        /// Reject transfers of `token_id` during its cooldown and start a new one. Nothing is recorded while there is no cooldown
+        pub fn record_transfer(&mut self, token_id: &Id, now: u64) -> Result<(), PSP34Error> {
+            if self.transfer_cooldown == 0 {
+                return Ok(());
+            }
+            if self.transfer_cooldown_remaining(token_id, now) > 0 {
+                return Err(PSP34Error::custom(codes::TRANSFER_COOLDOWN));
+            }
+            self.last_transfer_at.insert(token_id, &now);
+            Ok(())
+        }
+
+        /// Get the longest transfer `data` emitted as is
+        pub fn get_transfer_data_cap(&self) -> u32 {
+            if self.transfer_data_cap == 0 {
+                DEFAULT_TRANSFER_DATA_CAP
+            } else {
+                self.transfer_data_cap
+            }
+        }
+
+        /// Mark a token as non-transferable or transferable again
+        pub fn set_token_soulbound(&mut self, token_id: Id, soulbound: bool) {
+            if soulbound {
+                self.soulbound_tokens.insert(&token_id, &true);
+            } else {
+                self.soulbound_tokens.remove(&token_id);
+            }
+        }
+
+        /// Check token is soulbound or not
+        pub fn is_token_soulbound(&self, token_id: &Id) -> bool {
+            self.soulbound_tokens.get(token_id).unwrap_or(false)
+        }
+
+        /// Freeze an account so it can neither send nor receive tokens - Only with compliance enabled
+        pub fn freeze_account(&mut self, account: AccountId) -> Result<(), Error> {
+            if !self.compliance_enabled {
+                return Err(Error::custom(codes::COMPLIANCE_IS_NOT_ENABLED));
+            }
+            if self.frozen_accounts.insert(account, &()).is_some() {
+                return Err(Error::custom(codes::ACCOUNT_IS_ALREADY_FROZEN));
+            }
+            Ok(())
+        }
+
+        /// Unfreeze an account
+        pub fn unfreeze_account(&mut self, account: AccountId) -> Result<(), Error> {
+            if !self.frozen_accounts.contains(account) {
+                return Err(Error::custom(codes::ACCOUNT_IS_NOT_FROZEN));
+            }
+            self.frozen_accounts.remove(account);
+            Ok(())
+        }
+
+        /// Check account is frozen or not
+        pub fn is_account_frozen(&self, account: AccountId) -> bool {
+            self.compliance_enabled && self.frozen_accounts.contains(account)
+        }
+
+        /// Check none of `accounts` is frozen
+        pub fn check_not_frozen(&self, accounts: &[AccountId]) -> Result<(), Error> {
+            if !self.compliance_enabled {
+                return Ok(());
+            }
+            if accounts
+                .iter()
+                .any(|account| self.frozen_accounts.contains(account))
+            {
+                return Err(Error::AccountFrozen);
+            }
+            Ok(())
+        }
+
+        /// Record the terms of a new escrow
+        pub fn open_escrow(&mut self, token_id: &Id, escrow: &Escrow) -> Result<(), Error> {
+            if self.escrows.contains(token_id) {
+                return Err(Error::custom(codes::TOKEN_IS_ALREADY_ESCROWED));
+            }
+            self.escrows.insert(token_id, escrow);
+            Ok(())
+        }
+
+        /// Close the escrow of a token if `recipient` can still claim it
+        pub fn claim_escrow(
+            &mut self,
+            token_id: &Id,
+            recipient: AccountId,
+            now: u64,
+        ) -> Result<Escrow, Error> {
+            let escrow = self
+                .escrows
+                .get(token_id)
+                .ok_or(Error::custom(codes::NO_ESCROW_FOUND))?;
+            if escrow.recipient != recipient {
+                return Err(Error::custom(codes::CALLER_IS_NOT_THE_RECIPIENT));
+            }
+            if now > escrow.deadline {
+                return Err(Error::custom(codes::ESCROW_HAS_EXPIRED));
+            }
+            self.escrows.remove(token_id);
+            Ok(escrow)
+        }
+
+        /// Close the escrow of a token if `seller` can take it back
+        pub fn cancel_escrow(
+            &mut self,
+            token_id: &Id,
+            seller: AccountId,
+            now: u64,
+        ) -> Result<Escrow, Error> {
+            let escrow = self
+                .escrows
+                .get(token_id)
+                .ok_or(Error::custom(codes::NO_ESCROW_FOUND))?;
+            if escrow.seller != seller {
+                return Err(Error::custom(codes::CALLER_IS_NOT_THE_SELLER));
+            }
+            if !self.escrow_cancel_anytime && now <= escrow.deadline {
+                return Err(Error::custom(codes::ESCROW_HAS_NOT_EXPIRED));
+            }
+            self.escrows.remove(token_id);
+            Ok(escrow)
+        }
+
+        /// Get multiple  attributes
+        pub fn get_attributes(&self, token_id: Id, attributes: Vec<String>) -> Vec<String> {
+            let mut ret = Vec::<String>::new();
+            for attribute in attributes {
+                let value = self.get_attribute(token_id.clone(), attribute.into_bytes());
+
+                if let Some(value_in_bytes) = value {
+                    if let Ok(value_in_string) = String::from_utf8(value_in_bytes) {
+                        ret.push(value_in_string);
+                    } else {
+                        ret.push(String::from(""));
+                    }
+                } else {
+                    ret.push(String::from(""));
+                }
+            }
+            ret
+        }
+
+        /// Only Owner can set attributes of a token in a given language
+        pub fn set_localized_attributes(
+            &mut self,
+            token_id: Id,
+            lang: [u8; 2],
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            if token_id == Id::U64(0) || token_id == Id::U8(0) {
+                return Err(Error::InvalidInput);
+            }
+            if self.is_locked_nft(token_id.clone()) {
+                return Err(Error::custom(codes::TOKEN_IS_LOCKED));
+            }
+            if !self.supported_languages.contains(&lang) {
+                self.supported_languages.push(lang);
+            }
+            for (attribute, value) in metadata {
+                let attribute = attribute.into_bytes();
+                self.add_attribute_name(&attribute)?;
+                self.metadata.insert_attribute(
+                    &token_id,
+                    &localized_key(lang, &attribute),
+                    &value.into_bytes(),
+                );
+            }
+            Ok(())
+        }
+
+        /// Get an attribute in a given language, falling back to the default value
+        pub fn get_localized_attribute(
+            &self,
+            token_id: Id,
+            lang: [u8; 2],
+            key: String,
+        ) -> Option<String> {
+            let key = key.into_bytes();
+            self.metadata
+                .get_attribute(token_id.clone(), localized_key(lang, &key))
+                .or_else(|| self.get_attribute(token_id, key))
+                .and_then(|value_in_bytes| String::from_utf8(value_in_bytes).ok())
+        }
+
+        /// Get the languages used by localized attributes
+        pub fn get_supported_languages(&self) -> Vec<[u8; 2]> {
+            self.supported_languages.clone()
+        }
+
+        /// Get an attribute of a token. Tokens of an edition fall back to the attributes shared by the edition and to their copy number for `edition_number`
+        pub fn get_attribute(&self, token_id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            if let Some(value) = self.metadata.get_attribute(token_id.clone(), key.clone()) {
+                return Some(value);
+            }
+            if key == TIER_KEY {
+                if let Some(tier) = self
+                    .token_tiers
+                    .get(&token_id)
+                    .and_then(|tier_id| self.tiers.get(tier_id))
+                {
+                    return Some(tier.name.into_bytes());
+                }
+            }
+            if let Some((edition_id, copy_number)) = self.token_editions.get(&token_id) {
+                if key == EDITION_NUMBER_KEY {
+                    let mut buf = [0u8; MAX_U64_DIGITS];
+                    return Some(
+                        format_decimal(copy_number as u64, &mut buf)
+                            .as_bytes()
+                            .to_vec(),
+                    );
+                }
+                if let Some(value) = self.edition_attributes.get((edition_id, key.clone())) {
+                    return Some(value);
+                }
+            }
+            let version = self.token_templates.get(&token_id)?;
+            self.template_attributes.get((version, key))
+        }
+
+        /// Set the attributes every token minted from now on starts with. An empty template stops applying one
+        pub fn set_mint_attribute_template(
+            &mut self,
+            template: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            if template.len() > MAX_TEMPLATE_SIZE {
+                return Err(Error::InvalidInput);
+            }
+            if template.is_empty() {
+                self.mint_template_version = 0;
+                self.mint_template = template;
+                return Ok(());
+            }
+            let version = self
+                .last_template_version
+                .checked_add(1)
+                .ok_or(Error::custom(codes::CANNOT_INCREASE_TEMPLATE_VERSION))?;
+            for (attribute, value) in &template {
+                let attribute = attribute.clone().into_bytes();
+                self.add_attribute_name(&attribute)?;
+                self.template_attributes
+                    .insert((version, attribute), &value.clone().into_bytes());
+            }
+            self.mint_template_version = version;
+            self.last_template_version = version;
+            self.mint_template = template;
+            Ok(())
+        }
+
+        /// Apply the current mint attribute template to the new token `token_id`
+        pub fn apply_mint_template(&mut self, token_id: &Id) {
+            if self.mint_template_version != 0 {
+                self.token_templates
+                    .insert(token_id, &self.mint_template_version);
+            }
+        }
+
+        /// Define or update the tier `tier_id`. Its cap cannot go below what has been minted in it
+        pub fn define_tier(
+            &mut self,
+            tier_id: u8,
+            name: String,
+            max_supply: u64,
+        ) -> Result<(), Error> {
+            let minted = self.tiers.get(tier_id).map_or(0, |tier| tier.minted);
+            if max_supply < minted {
+                return Err(Error::InvalidInput);
+            }
+            self.add_attribute_name(&TIER_KEY.to_vec())?;
+            self.tiers.insert(
+                tier_id,
+                &Tier {
+                    name,
+                    max_supply,
+                    minted,
+                },
+            );
+            Ok(())
+        }
+
+        /// Reserve `count` tokens of the supply of the tier `tier_id`
+        pub fn take_tier_supply(&mut self, tier_id: u8, count: u64) -> Result<(), Error> {
+            let mut tier = self
+                .tiers
+                .get(tier_id)
+                .ok_or(Error::custom(codes::TIER_DOES_NOT_EXIST))?;
+            let minted = tier.minted.checked_add(count).ok_or(Error::TierSoldOut)?;
+            if minted > tier.max_supply {
+                return Err(Error::TierSoldOut);
+            }
+            tier.minted = minted;
+            self.tiers.insert(tier_id, &tier);
+            Ok(())
+        }
+
+        /// Create an edition of at most `max_copies` tokens sharing `metadata`
+        pub fn create_edition(
+            &mut self,
+            edition_id: u32,
+            max_copies: u32,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            if max_copies == 0 {
+                return Err(Error::InvalidInput);
+            }
+            if self.editions.contains(edition_id) {
+                return Err(Error::custom(codes::EDITION_ALREADY_EXISTS));
+            }
+            self.add_attribute_name(&EDITION_NUMBER_KEY.to_vec())?;
+            for (attribute, value) in metadata {
+                let attribute = attribute.into_bytes();
+                if attribute == EDITION_NUMBER_KEY {
+                    return Err(Error::InvalidInput);
+                }
+                self.add_attribute_name(&attribute)?;
+                self.edition_attributes
+                    .insert((edition_id, attribute), &value.into_bytes());
+            }
+            self.editions.insert(
+                edition_id,
+                &Edition {
+                    max_copies,
+                    minted: 0,
+                },
+            );
+            Ok(())
+        }
+
+        /// Take the next copy number of an edition
+        pub fn next_edition_copy(&mut self, edition_id: u32) -> Result<u32, Error> {
+            let mut edition = self
+                .editions
+                .get(edition_id)
+                .ok_or(Error::custom(codes::EDITION_DOES_NOT_EXIST))?;
+            if edition.minted >= edition.max_copies {
+                return Err(Error::custom(codes::EDITION_SOLD_OUT));
+            }
+            edition.minted += 1;
+            self.editions.insert(edition_id, &edition);
+            Ok(edition.minted)
+        }
+
+        /// Get multiple attributes, None for the ones that are not set
+        pub fn get_attributes_opt(
+            &self,
+            token_id: Id,
+            attributes: Vec<String>,
+        ) -> Vec<Option<String>> {
+            attributes
+                .into_iter()
+                .map(|attribute| {
+                    self.get_attribute(token_id.clone(), attribute.into_bytes())
+                        .and_then(|value_in_bytes| String::from_utf8(value_in_bytes).ok())
+                })
+                .collect()
+        }
+
+        /// Get the attributes in `required` that have no value on the token
+        pub fn missing_attributes(&self, token_id: Id, required: Vec<String>) -> Vec<String> {
+            required
+                .into_iter()
+                .filter(|attribute| {
+                    self.get_attribute(token_id.clone(), attribute.clone().into_bytes())
+                        .is_none()
+                })
+                .collect()
+        }
+
+        /// Get Attribute Count
+        pub fn get_attribute_count(&self) -> u32 {
+            self.attribute_count
+        }
+        /// Get Attribute Name
+        pub fn get_attribute_name(&self, index: u32) -> String {
+            let attribute = self.attribute_names.get(index);
+
+            if let Some(value_in_bytes) = attribute {
+                String::from_utf8(value_in_bytes).unwrap_or_default()
+            } else {
+                String::from("")
+            }
+        }
+
+        /// Get Attribute Name, telling an index outside `1..=attribute_count` apart from a name that is not valid UTF-8
+        pub fn try_get_attribute_name(&self, index: u32) -> Result<String, Error> {
+            let value_in_bytes = self
+                .attribute_names
+                .get(index)
+                .ok_or(Error::IndexOutOfRange)?;
+            String::from_utf8(value_in_bytes).map_err(|_| Error::InvalidUtf8)
+        }
+
+        /// Get all registered Attribute Names in registration order
+        pub fn get_all_attribute_names(&self) -> Vec<String> {
+            (1..=self.attribute_count)
+                .map(|index| self.get_attribute_name(index))
+                .collect()
+        }
+
+        /// Get Attribute Index from its name
+        pub fn get_attribute_index(&self, name: String) -> Option<u32> {
+            let name = name.into_bytes();
+            if let Some(index) = self.attribute_index.get(&name) {
+                return Some(index);
+            }
+            // Names registered before the reverse index existed are not backfilled yet
+            self._find_attribute_index(&name)
+        }
+
+        /// Set a URI for a token that replaces the one built from the base URI
+        pub fn set_token_uri(&mut self, token_id: Id, uri: String) -> Result<(), Error> {
+            if self.is_uri_locked(&token_id) {
+                return Err(Error::custom(codes::TOKEN_URI_IS_LOCKED));
+            }
+            self.token_uris.insert(&token_id, &uri);
+            Ok(())
+        }
+
+        /// Lock the URI of a token, leaving its attributes mutable
+        pub fn lock_uri(&mut self, token_id: Id) {
+            self.uri_locked.insert(&token_id, &true);
+        }
+
+        /// Check the URI of a token is locked
+        pub fn is_uri_locked(&self, token_id: &Id) -> bool {
+            self.uri_locked.get(token_id).unwrap_or(false)
+        }
+
+        /// Get URI from token ID
+        pub fn token_uri(&self, token_id: u64) -> String {
+            if let Some(uri) = self.token_uris.get(Id::U64(token_id)) {
+                return uri;
+            }
+            let mut token_uri = self
+                ._base_uri_for(token_id)
+                .and_then(|value_in_bytes| String::from_utf8(value_in_bytes).ok())
+                .unwrap_or_default();
+
+            let mut digits = [0u8; MAX_U64_DIGITS];
+            let token_id = format_decimal(token_id, &mut digits);
+            token_uri.reserve_exact(token_id.len() + TOKEN_URI_SUFFIX.len());
+            token_uri.push_str(token_id);
+            token_uri.push_str(TOKEN_URI_SUFFIX);
+            token_uri
+        }
+
+        /// Set the base URI of the ids `start` to `end` (inclusive). Ranges cannot overlap and are kept sorted by `start`
+        pub fn set_base_uri_for_range(
+            &mut self,
+            start: u64,
+            end: u64,
+            uri: String,
+        ) -> Result<(), Error> {
+            if start > end || uri.is_empty() {
+                return Err(Error::InvalidInput);
+            }
+            if self.uri_ranges.len() >= MAX_URI_RANGES {
+                return Err(Error::custom(codes::TOO_MANY_URI_RANGES));
+            }
+            let position = self
+                .uri_ranges
+                .partition_point(|(range_start, _, _)| *range_start < start);
+            let overlaps_previous = position
+                .checked_sub(1)
+                .and_then(|previous| self.uri_ranges.get(previous))
+                .is_some_and(|(_, previous_end, _)| *previous_end >= start);
+            let overlaps_next = self
+                .uri_ranges
+                .get(position)
+                .is_some_and(|(next_start, _, _)| *next_start <= end);
+            if overlaps_previous || overlaps_next {
+                return Err(Error::UriRangeOverlap);
+            }
+            self.uri_ranges.insert(position, (start, end, uri));
+            Ok(())
+        }
+
+        /// Remove the range starting at `start`, its ids fall back to the base URI
+        pub fn remove_uri_range(&mut self, start: u64) -> Result<(), Error> {
+            let position = self
+                .uri_ranges
+                .iter()
+                .position(|(range_start, _, _)| *range_start == start)
+                .ok_or(Error::InvalidInput)?;
+            self.uri_ranges.remove(position);
+            Ok(())
+        }
+
+        /// Get the base URI of the range containing `token_id`, or the collection base URI
+        fn _base_uri_for(&self, token_id: u64) -> Option<Vec<u8>> {
+            let position = self
+                .uri_ranges
+                .partition_point(|(start, _, _)| *start <= token_id);
+            if let Some((_, end, uri)) = position
+                .checked_sub(1)
+                .and_then(|range| self.uri_ranges.get(range))
+            {
+                if token_id <= *end {
+                    return Some(uri.clone().into_bytes());
+                }
+            }
+            self.metadata
+                .get_attribute(Id::U8(0), String::from("baseURI").into_bytes())
+        }
+
+        /// Get the token URI, failing when the token has no URI of its own and no base URI is set
+        pub fn try_token_uri(&self, token_id: u64) -> Result<String, Error> {
+            if self.token_uris.contains(Id::U64(token_id)) {
+                return Ok(self.token_uri(token_id));
+            }
+            match self._base_uri_for(token_id) {
+                Some(base_uri) if !base_uri.is_empty() => Ok(self.token_uri(token_id)),
+                _ => Err(Error::BaseUriNotSet),
+            }
+        }
+
+        fn _set_attributes(
+            &mut self,
+            token_id: Id,
+            metadata: Vec<(String, String)>,
+        ) -> Result<(), Error> {
+            for (attribute, value) in &metadata {
+                self.add_attribute_name(&attribute.clone().into_bytes())?;
+                self.metadata.set_attribute(
+                    token_id.clone(),
+                    attribute.clone().into_bytes(),
+                    value.clone().into_bytes(),
+                )?;
+            }
+            Ok(())
+        }
+
+        fn add_attribute_name(&mut self, attribute_input: &Vec<u8>) -> Result<(), Error> {
+            if let Ok(attr_input) = String::from_utf8((*attribute_input).clone()) {
+                if self.attribute_index.contains(attribute_input) {
+                    return Ok(());
+                }
+                if self.is_attribute.get(&attr_input).is_some() {
+                    // Registered before the reverse index existed, backfill it
+                    if let Some(index) = self._find_attribute_index(attribute_input) {
+                        self.attribute_index.insert(attribute_input, &index);
+                    }
+                    return Ok(());
+                }
+                if self.attribute_count >= self.get_max_attributes() {
+                    return Err(Error::TooManyAttributes);
+                }
+                if let Some(attribute_count) = self.attribute_count.checked_add(1) {
+                    self.attribute_count = attribute_count;
+                    self.attribute_names
+                        .insert(self.attribute_count, attribute_input);
+                    self.attribute_index
+                        .insert(attribute_input, &self.attribute_count);
+                    self.is_attribute.insert(&attr_input, &true);
+                    Ok(())
+                } else {
+                    Err(Error::custom(codes::FAIL_TO_INCREASE_ATTRIBUTE_COUNT))
+                }
             } else {
-                return Err(Error::Custom(String::from("Attribute input error")));
+                Err(Error::custom(codes::ATTRIBUTE_INPUT_ERROR))
             }
         }
+
+        fn _find_attribute_index(&self, attribute_input: &Vec<u8>) -> Option<u32> {
+            (1..=self.attribute_count)
+                .find(|index| self.attribute_names.get(index).as_ref() == Some(attribute_input))
+        }
     }
 }
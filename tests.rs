@@ -0,0 +1,112 @@
+//! Off-chain tests of the contract messages.
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::psp34_nft::Psp34Nft;
+use crate::{codes, Error, Id, Psp34Traits};
+use ink::env::{test, DefaultEnvironment};
+use ink::prelude::{string::String, vec::Vec};
+use ink::primitives::AccountId;
+
+/// Account the contract under test is deployed at
+const CONTRACT: [u8; 32] = [0xC0; 32];
+
+fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+    test::default_accounts::<DefaultEnvironment>()
+}
+
+fn set_caller(caller: AccountId) {
+    test::set_caller::<DefaultEnvironment>(caller);
+}
+
+/// Deploys a collection owned by alice, who stays the caller
+fn deploy() -> Psp34Nft {
+    test::set_callee::<DefaultEnvironment>(AccountId::from(CONTRACT));
+    set_caller(accounts().alice);
+    Psp34Nft::new(accounts().alice, String::from("Test"), String::from("TEST"))
+}
+
+fn attrs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(key, value)| (String::from(*key), String::from(*value)))
+        .collect()
+}
+
+fn attribute(contract: &Psp34Nft, id: u64, key: &str) -> String {
+    contract
+        .get_attributes(Id::U64(id), ink::prelude::vec![String::from(key)])
+        .remove(0)
+}
+
+#[ink::test]
+fn admin_set_attributes_bypasses_lock_until_disabled() {
+    let mut contract = deploy();
+    contract.mint().unwrap();
+    contract.lock(Id::U64(1)).unwrap();
+    assert!(contract.is_admin_override_enabled());
+
+    contract
+        .admin_set_attributes(Id::U64(1), attrs(&[("eyes", "blue")]))
+        .unwrap();
+    assert_eq!(attribute(&contract, 1, "eyes"), "blue");
+
+    contract.disable_admin_override().unwrap();
+    assert_eq!(
+        contract.admin_set_attributes(Id::U64(1), attrs(&[("eyes", "red")])),
+        Err(Error::custom(codes::ADMIN_OVERRIDE_IS_DISABLED))
+    );
+    assert_eq!(
+        contract.disable_admin_override(),
+        Err(Error::custom(codes::ADMIN_OVERRIDE_IS_ALREADY_DISABLED))
+    );
+    assert_eq!(attribute(&contract, 1, "eyes"), "blue");
+}
+
+#[ink::test]
+fn admin_set_attributes_is_owner_only() {
+    let mut contract = deploy();
+    contract.mint().unwrap();
+    set_caller(accounts().bob);
+    assert!(contract
+        .admin_set_attributes(Id::U64(1), attrs(&[("eyes", "blue")]))
+        .is_err());
+    assert!(contract.disable_admin_override().is_err());
+    assert!(contract.is_admin_override_enabled());
+}
+
+/// Storage reads and writes made by `f` on the contract deployed at `account`
+fn storage_rw<F: FnOnce()>(account: [u8; 32], f: F) -> (usize, usize) {
+    let account = AccountId::from(account);
+    let (reads_before, writes_before) =
+        test::get_contract_storage_rw::<DefaultEnvironment>(&account);
+    f();
+    let (reads_after, writes_after) = test::get_contract_storage_rw::<DefaultEnvironment>(&account);
+    (reads_after - reads_before, writes_after - writes_before)
+}
+
+#[ink::test]
+fn mint_with_attributes_writes_less_than_mint_then_set() {
+    let metadata = attrs(&[("eyes", "blue"), ("hat", "red"), ("mood", "calm")]);
+
+    let mut fast = deploy();
+    let (fast_reads, fast_writes) = storage_rw(CONTRACT, || {
+        fast.mint_with_attributes(metadata.clone()).unwrap();
+    });
+
+    let other = [0xC1; 32];
+    test::set_callee::<DefaultEnvironment>(AccountId::from(other));
+    let mut slow = Psp34Nft::new(accounts().alice, String::from("Test"), String::from("TEST"));
+    let (slow_reads, slow_writes) = storage_rw(other, || {
+        slow.mint().unwrap();
+        slow.set_multiple_attributes(Id::U64(1), metadata.clone())
+            .unwrap();
+    });
+
+    // The keys of the new token are indexed once instead of once per attribute
+    assert_eq!(fast_writes + metadata.len() - 1, slow_writes);
+    assert!(fast_reads < slow_reads);
+    for (key, value) in &metadata {
+        assert_eq!(attribute(&fast, 1, key), *value);
+        assert_eq!(attribute(&slow, 1, key), *value);
+    }
+}
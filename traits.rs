@@ -25,6 +25,7 @@ pub trait PSP34 {
     /// Returns `true` if the operator is approved by the owner to withdraw `id` token.
     ///
     /// If `id` is `None`, returns `true` if the operator is approved to withdraw all owner's tokens.
+    /// Passing the `collection_id` as `id` is the same as passing `None`.
     #[ink(message)]
     fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
 
@@ -44,11 +45,15 @@ pub trait PSP34 {
 
     /// Approves `operator` to withdraw  the `id` token from the caller's account.
     /// If `id` is `None` approves or disapproves the operator for all tokens of the caller.
+    /// Passing the `collection_id` as `id` is the same as passing `None`; any other `id`
+    /// must be an existing token.
     ///
     /// An `Approval` event is emitted.
     ///
     /// # Errors
     ///
+    /// Returns `TokenNotExists` error if `id` is neither an existing token nor the collection id.
+    ///
     /// Returns `SelfApprove` error if it is self approve.
     ///
     /// Returns `NotApproved` error if caller is not owner of `id`.
@@ -136,7 +141,7 @@ pub trait Psp34Traits {
     /// This function sets the baseURI for the NFT contract. Only Contract Owner can perform this function. baseURI is the location of the metadata files if the NFT collection use external source to keep their NFT artwork. ArtZero uses IPFS by default, the baseURI can have format like this: ipfs://<hash_ID>/
     #[ink(message)]
     fn set_base_uri(&mut self, uri: String) -> Result<(), Error>;
-    /// This function set the attributes to each NFT. Only Contract Owner can perform this function. The metadata input is an array of [(attribute, value)]. The attributes in ArtZero platform are the NFT traits.
+    /// This function set the attributes to each NFT. Only Contract Owner can perform this function, except for attributes named `prefix:<name>` of a registered namespace, which only the namespace writer can set. The metadata input is an array of [(attribute, value)]. The attributes in ArtZero platform are the NFT traits.
     #[ink(message)]
     fn set_multiple_attributes(
         &mut self,
@@ -149,7 +154,7 @@ pub trait Psp34Traits {
     /// This function return how many unique attributes in the contract
     #[ink(message)]
     fn get_attribute_count(&self) -> u32;
-    /// This function return the attribute name using attribute index. Beacause attributes of an NFT can be set to anything by Contract Owner, AztZero uses this function to get all attributes of an NFT
+    /// This function return the attribute name using attribute index. Beacause attributes of an NFT can be set to anything by Contract Owner, AztZero uses this function to get all attributes of an NFT. Indices start at 1 and an empty string is returned for unknown indices, see `try_get_attribute_name`
     #[ink(message)]
     fn get_attribute_name(&self, index: u32) -> String;
     /// This function return the metadata location of an NFT. The format is baseURI/<token_id>.json
@@ -169,5 +174,5 @@ pub trait Psp34Traits {
     fn get_locked_token_count(&self) -> u64;
 
     #[ink(message)]
-    fn get_owner(&self) -> AccountId;
+    fn get_owner(&self) -> Option<AccountId>;
 }